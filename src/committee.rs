@@ -0,0 +1,169 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+
+/// How well a committee of winners serves the voters. Utility measures are
+/// averages over voters of their true utilities. The rest are measured on
+/// approval ballots (cit, cand), as for Range with 2 ranks.
+///
+/// A group of voters is l-cohesive when it's at least l / seats of the voters,
+/// and all its members approve the same l candidates. Such a group deserves
+/// about l seats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitteeMetrics {
+    /// The sum of a voter's utilities for the winners
+    pub total_utility: f64,
+    /// Each voter's utilities for the winners, best first, weighted by
+    /// 1, 1/2, 1/3, ...
+    pub rank_utility: f64,
+    /// Chamberlin-Courant coverage: the fraction of voters approving a winner
+    pub coverage: f64,
+    /// For l = 1 to seats, the lowest average number of approved winners of
+    /// any l-cohesive group, or None if there is no such group.
+    /// Proportional committees give l-cohesive groups around l winners.
+    pub prop_degree: Vec<Option<f64>>,
+    /// Justified representation: every 1-cohesive group has a member approving
+    /// a winner.
+    pub jr: bool,
+    /// Extended justified representation: every l-cohesive group has a member
+    /// approving at least l winners.
+    pub ejr: bool,
+}
+
+impl CommitteeMetrics {
+    pub fn compute(
+        utilities: &Array2<f64>,
+        approvals: &Array2<i32>,
+        committee: &[usize],
+        seats: usize,
+    ) -> CommitteeMetrics {
+        let ncit = utilities.nrows();
+        let mut total_utility = 0.0;
+        let mut rank_utility = 0.0;
+        let mut nsatisfied = vec![0; ncit]; // Winners each voter approves
+        let mut winner_utils = Vec::with_capacity(committee.len());
+        for icit in 0..ncit {
+            winner_utils.clear();
+            winner_utils.extend(committee.iter().map(|&c| utilities[(icit, c)]));
+            winner_utils.sort_by(|a, b| b.total_cmp(a));
+            total_utility += winner_utils.iter().sum::<f64>();
+            rank_utility += winner_utils
+                .iter()
+                .enumerate()
+                .map(|(i, u)| u / (i + 1) as f64)
+                .sum::<f64>();
+            nsatisfied[icit] = committee
+                .iter()
+                .filter(|&&c| approvals[(icit, c)] > 0)
+                .count();
+        }
+        let ncovered = nsatisfied.iter().filter(|&&n| n > 0).count();
+
+        let mut metrics = CommitteeMetrics {
+            total_utility: total_utility / ncit as f64,
+            rank_utility: rank_utility / ncit as f64,
+            coverage: ncovered as f64 / ncit as f64,
+            prop_degree: vec![None; seats],
+            jr: true,
+            ejr: true,
+        };
+        let everyone: Vec<usize> = (0..ncit).collect();
+        metrics.check_groups(approvals, &nsatisfied, seats, 0, 0, &everyone);
+        metrics
+    }
+
+    /// Checks the group of voters approving the nchosen candidates chosen so
+    /// far, then every group approving those and another candidate from first on.
+    fn check_groups(
+        &mut self,
+        approvals: &Array2<i32>,
+        nsatisfied: &[usize],
+        seats: usize,
+        nchosen: usize,
+        first: usize,
+        group: &[usize],
+    ) {
+        let ncit = approvals.nrows();
+        if nchosen > 0 {
+            let l = nchosen;
+            // Its least satisfied members are the worst l-cohesive group within it.
+            let min_size = (l * ncit).div_ceil(seats);
+            let mut sats: Vec<usize> = group.iter().map(|&icit| nsatisfied[icit]).collect();
+            sats.sort_unstable();
+            let avg = sats[..min_size].iter().sum::<usize>() as f64 / min_size as f64;
+            let pd = &mut self.prop_degree[l - 1];
+            *pd = Some(pd.map_or(avg, |pd| pd.min(avg)));
+            if sats[min_size - 1] < l {
+                self.ejr = false;
+                if l == 1 {
+                    self.jr = false;
+                }
+            }
+        }
+        if nchosen == seats || !is_cohesive(group.len(), nchosen + 1, ncit, seats) {
+            return;
+        }
+        for icand in first..approvals.ncols() {
+            let subgroup: Vec<usize> = group
+                .iter()
+                .copied()
+                .filter(|&icit| approvals[(icit, icand)] > 0)
+                .collect();
+            if is_cohesive(subgroup.len(), nchosen + 1, ncit, seats) {
+                self.check_groups(
+                    approvals,
+                    nsatisfied,
+                    seats,
+                    nchosen + 1,
+                    icand + 1,
+                    &subgroup,
+                );
+            }
+        }
+    }
+}
+
+/// Whether a group of this size, approving l candidates in common, is l-cohesive.
+fn is_cohesive(size: usize, l: usize, ncit: usize, seats: usize) -> bool {
+    size * seats >= l * ncit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_committee_metrics() {
+        // Four voters approve candidates 0 and 1, and two approve 2.
+        let approvals = array![
+            [1, 1, 0],
+            [1, 1, 0],
+            [1, 1, 0],
+            [1, 1, 0],
+            [0, 0, 1],
+            [0, 0, 1],
+        ];
+        let utilities = approvals.mapv(|a| a as f64);
+
+        // With three seats, a committee of 0 and 1 leaves out a 1-cohesive group.
+        let metrics = CommitteeMetrics::compute(&utilities, &approvals, &[0, 1], 3);
+        assert_eq!(metrics.total_utility, 8.0 / 6.0);
+        assert_eq!(metrics.rank_utility, 4.0 * 1.5 / 6.0);
+        assert_eq!(metrics.coverage, 4.0 / 6.0);
+        assert_eq!(metrics.prop_degree, vec![Some(0.0), Some(2.0), None]);
+        assert!(!metrics.jr);
+        assert!(!metrics.ejr);
+
+        let metrics = CommitteeMetrics::compute(&utilities, &approvals, &[0, 2], 3);
+        assert_eq!(metrics.coverage, 1.0);
+        assert_eq!(metrics.prop_degree, vec![Some(1.0), Some(1.0), None]);
+        assert!(metrics.jr);
+        assert!(!metrics.ejr);
+
+        let metrics = CommitteeMetrics::compute(&utilities, &approvals, &[0, 1, 2], 3);
+        assert_eq!(metrics.prop_degree, vec![Some(1.0), Some(2.0), None]);
+        assert!(metrics.ejr);
+    }
+}
@@ -6,8 +6,11 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::considerations::Consideration;
-use crate::methods::{Method, MultiWinMethod};
+use crate::considerations::{first_factions, Combine, Consideration};
+use crate::methods::{Method, MultiWinMethod, Strategy};
+use crate::perception::Perception;
+use crate::sweep::SweepAxis;
+use crate::yee::Yee;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
@@ -16,9 +19,31 @@ pub struct Config {
     pub candidates: usize,
     pub primary_candidates: Option<usize>,
     pub considerations: Vec<Consideration>,
+    /// How the considerations' utilities are combined
+    #[serde(default)]
+    pub combine: Combine,
     pub methods: Vec<Method>,
+    /// Strategic fractions for each voter faction, one per faction, used by
+    /// every strategic method. Factions come from the first consideration that
+    /// divides voters: the sides of a polarized Issue, or Irrational camps.
+    pub faction_strat_frac: Option<Vec<f64>>,
+    /// Voters cast ballots on noisy, biased perceptions of their utilities
+    pub perception: Option<Perception>,
+    /// Seeds the random number generator. Without one, a random seed is chosen
+    /// and recorded with the config in the output file.
+    pub seed: Option<u64>,
+    /// Settings for the yee subcommand
+    pub yee: Option<Yee>,
+    /// Runs every combination of these config values, instead of the config alone
+    pub sweep: Option<Vec<SweepAxis>>,
     #[serde(default = "default_primary")]
     pub primary_method: MultiWinMethod,
+    /// Committees to elect from the general election's candidates, with their
+    /// quality written out and summarized like single-winner methods
+    #[serde(default)]
+    pub multi_winner_methods: Vec<MultiWinMethod>,
+    /// The number of winners each multi-winner method elects
+    pub seats: Option<usize>,
 }
 
 fn default_primary() -> MultiWinMethod {
@@ -36,9 +61,93 @@ impl Config {
         // let reader = BufReader::new(file);
 
         // Read the TOML contents of the file as an instance of `Config`.
-        let config = toml::from_str(&config_str)?;
+        let config: Config = toml::from_str(&config_str)?;
+        config.validate()?;
 
         // Return the `User`.
         Ok(config)
     }
+
+    /// Checks values that deserialize fine but make no sense.
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(fractions) = &self.faction_strat_frac {
+            if fractions.iter().any(|f| !(0.0..=1.0).contains(f)) {
+                return Err(format!(
+                    "faction_strat_frac values must be between 0 and 1, got {:?}",
+                    fractions
+                )
+                .into());
+            }
+            // Their own fractions would be ignored, but still name their columns.
+            if let Some(method) = self
+                .methods
+                .iter()
+                .find(|m| matches!(m.strat(), Strategy::Strategic { fraction } if fraction != 1.0))
+            {
+                return Err(format!(
+                    "with faction_strat_frac, strategic methods take their fractions from it, \
+                     so must be plain \"Strategic\", got {:?}",
+                    method
+                )
+                .into());
+            }
+        }
+        if !self.multi_winner_methods.is_empty() {
+            match self.seats {
+                Some(seats) if seats >= 1 && seats <= self.candidates => (),
+                Some(seats) => {
+                    return Err(format!(
+                        "seats must be from 1 to the number of candidates, {}, got {}",
+                        self.candidates, seats
+                    )
+                    .into())
+                }
+                None => return Err("multi_winner_methods need a number of seats".into()),
+            }
+        }
+        if let Some(perception) = &self.perception {
+            perception.validate()?;
+        }
+        for consideration in self.considerations.iter() {
+            consideration.validate()?;
+        }
+        self.combine.validate(&self.considerations)?;
+        if let Some(fractions) = &self.faction_strat_frac {
+            let nfactions = first_factions(&self.considerations);
+            if fractions.len() != nfactions {
+                return Err(format!(
+                    "faction_strat_frac needs one value for each of the {} voter factions, got {:?}",
+                    nfactions, fractions
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faction_strat_frac() {
+        let config = |fractions: &str| {
+            toml::from_str::<Config>(&format!(
+                r#"
+                voters = 11
+                candidates = 3
+                faction_strat_frac = {}
+                considerations = [{{ Issues = [{{ sigma = 1.0, halfcsep = 1.0 }}] }}]
+                methods = [{{ Plurality = {{ strat = "Strategic" }} }}]
+                "#,
+                fractions
+            ))
+            .unwrap()
+        };
+        // The polarized issue divides voters into two factions.
+        assert!(config("[0.8, 0.1]").validate().is_ok());
+        assert!(config("[0.8, 0.1, 0.5]").validate().is_err());
+        assert!(config("[0.8]").validate().is_err());
+    }
 }
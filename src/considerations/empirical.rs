@@ -0,0 +1,289 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int32Type, Int64Type};
+use arrow_array::ArrayRef;
+use arrow_schema::DataType;
+use ndarray::Array2;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use super::issues::{new_issues_sim, Issue, IssuesSim, Metric, Salience, UtilityFn};
+use crate::sim::Sim;
+
+/// Empirical positions, such as ideal points estimated from surveys or roll-call
+/// votes. Each election samples its voters from the rows of a file, with
+/// replacement, and utilities fall off with the distance between voters and
+/// candidates just as for Issues.
+///
+/// Files are CSV with a header row, or Parquet if named *.parquet. Paths are
+/// relative to the working directory. Rows missing any position (empty, NA, or
+/// null) are skipped.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Empirical {
+    /// File of voter positions, one row per respondent
+    pub voters: PathBuf,
+    /// File of candidate positions, with the same columns. Without it, candidates
+    /// are drawn from the voter file's rows. Each election's candidates are
+    /// distinct rows as long as there are enough.
+    pub candidates: Option<PathBuf>,
+    /// The columns holding positions, one per issue. Defaults to every column
+    /// of the voter file.
+    pub columns: Option<Vec<String>>,
+    #[serde(default)]
+    pub utility_fn: UtilityFn,
+    #[serde(default)]
+    pub metric: Metric,
+    pub salience: Option<Salience>,
+    /// Voter and candidate positions, read by validate and shared by every
+    /// election, including clones of the config
+    #[serde(skip)]
+    positions: OnceLock<Positions>,
+}
+
+type Positions = (Arc<Array2<f64>>, Arc<Array2<f64>>);
+
+impl Empirical {
+    pub fn new_sim(&self, sim: &Sim) -> IssuesSim {
+        let (voters, candidates) = self
+            .positions
+            .get()
+            .cloned()
+            .expect("Empirical positions are loaded by validate");
+        let mut issues_sim = new_issues_sim(vec![Issue::plain(); voters.ncols()], sim);
+        issues_sim.set_distance(self.utility_fn, self.metric, self.salience.as_ref());
+        issues_sim.resample_rows("empirical", voters, candidates);
+        issues_sim
+    }
+
+    /// Checks that the files can be read, and that salience matches their columns.
+    /// The positions are read only the first time.
+    pub fn validate(&self) -> Result<(), String> {
+        self.utility_fn.validate()?;
+        if self.positions.get().is_none() {
+            let (voters, candidates) = self.load()?;
+            let _ = self.positions.set((Arc::new(voters), Arc::new(candidates)));
+        }
+        let (voters, _candidates) = self.positions.get().unwrap();
+        match &self.salience {
+            Some(salience) => salience.validate(voters.ncols()),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads voter and candidate positions, one row each.
+    fn load(&self) -> Result<(Array2<f64>, Array2<f64>), String> {
+        let (names, voters) = read_positions(&self.voters, self.columns.as_deref())?;
+        let candidates = match &self.candidates {
+            Some(path) => read_positions(path, Some(&names))?.1,
+            None => voters.clone(),
+        };
+        Ok((voters, candidates))
+    }
+}
+
+/// Reads the named columns, or all of them, returning the column names and the
+/// complete rows.
+fn read_positions(
+    path: &Path,
+    columns: Option<&[String]>,
+) -> Result<(Vec<String>, Array2<f64>), String> {
+    let is_parquet = path.extension().is_some_and(|ext| ext == "parquet");
+    let (names, rows) = if is_parquet {
+        read_parquet(path, columns)
+    } else {
+        read_csv(path, columns)
+    }
+    .map_err(|err| format!("Can't read positions from {}: {}", path.display(), err))?;
+
+    let ncols = names.len();
+    let complete: Vec<f64> = rows
+        .iter()
+        .filter(|row| row.iter().all(|x| x.is_some_and(|x| !x.is_nan())))
+        .flatten()
+        .flatten()
+        .copied()
+        .collect();
+    let nrows = complete.len() / ncols.max(1);
+    if ncols == 0 || nrows == 0 {
+        return Err(format!("No positions found in {}", path.display()));
+    }
+    if nrows < rows.len() {
+        log::info!(
+            "Skipped {} incomplete rows of {}",
+            rows.len() - nrows,
+            path.display()
+        );
+    }
+    let positions = Array2::from_shape_vec((nrows, ncols), complete).unwrap();
+    Ok((names, positions))
+}
+
+type Rows = Vec<Vec<Option<f64>>>;
+
+fn read_csv(
+    path: &Path,
+    columns: Option<&[String]>,
+) -> Result<(Vec<String>, Rows), Box<dyn Error>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let names: Vec<String> = match columns {
+        Some(columns) => columns.to_vec(),
+        None => headers.iter().map(|h| h.trim().to_string()).collect(),
+    };
+    let indices = names
+        .iter()
+        .map(|name| {
+            headers
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| format!("no column {}", name))
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let row = indices
+            .iter()
+            .zip(names.iter())
+            .map(|(&i, name)| {
+                let field = record.get(i).unwrap_or("").trim();
+                if field.is_empty() || field == "NA" {
+                    Ok(None)
+                } else {
+                    field
+                        .parse::<f64>()
+                        .map(Some)
+                        .map_err(|_| format!("{} in column {} isn't a number", field, name))
+                }
+            })
+            .collect::<Result<Vec<Option<f64>>, String>>()?;
+        rows.push(row);
+    }
+    Ok((names, rows))
+}
+
+fn read_parquet(
+    path: &Path,
+    columns: Option<&[String]>,
+) -> Result<(Vec<String>, Rows), Box<dyn Error>> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+    let names: Vec<String> = match columns {
+        Some(columns) => columns.to_vec(),
+        None => builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect(),
+    };
+    let mut rows = Vec::new();
+    for batch in builder.build()? {
+        let batch = batch?;
+        let values = names
+            .iter()
+            .map(|name| {
+                let array = batch
+                    .column_by_name(name)
+                    .ok_or_else(|| format!("no column {}", name))?;
+                float_values(array).ok_or_else(|| format!("column {} isn't numeric", name))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        for irow in 0..batch.num_rows() {
+            rows.push(values.iter().map(|column| column[irow]).collect());
+        }
+    }
+    Ok((names, rows))
+}
+
+fn float_values(array: &ArrayRef) -> Option<Vec<Option<f64>>> {
+    Some(match array.data_type() {
+        DataType::Float64 => array.as_primitive::<Float64Type>().iter().collect(),
+        DataType::Float32 => array
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(|x| x.map(f64::from))
+            .collect(),
+        DataType::Int64 => array
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(|x| x.map(|x| x as f64))
+            .collect(),
+        DataType::Int32 => array
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(|x| x.map(f64::from))
+            .collect(),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::considerations::Consideration;
+    use crate::sim::trial_rng;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_empirical_positions() {
+        let dir = std::env::temp_dir().join(format!("vote_sim_empirical_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let voters = dir.join("voters.csv");
+        std::fs::write(
+            &voters,
+            "id,dim1,dim2\n1,0.5,-0.25\n2,NA,1.0\n3,-0.5,0.25\n4,,0.0\n",
+        )
+        .unwrap();
+        let candidates = dir.join("candidates.csv");
+        std::fs::write(&candidates, "dim2,dim1\n0.0,1.0\n0.0,-1.0\n").unwrap();
+
+        let config = format!(
+            "[Empirical]\nvoters = {:?}\ncandidates = {:?}\ncolumns = [\"dim1\", \"dim2\"]",
+            voters, candidates
+        );
+        let c: Consideration = toml::from_str(&config).unwrap();
+        assert!(c.validate().is_ok());
+        let Consideration::Empirical(empirical) = &c else {
+            panic!("Expected Empirical");
+        };
+        let (voter_rows, cand_rows) = empirical.load().unwrap();
+        assert_eq!(voter_rows, ndarray::array![[0.5, -0.25], [-0.5, 0.25]]);
+        assert_eq!(cand_rows, ndarray::array![[1.0, 0.0], [-1.0, 0.0]]);
+
+        // Both candidates are drawn, and every voter is as near to one of
+        // them as the other voter.
+        let sim = Sim::new(2, 20);
+        let mut axis = c.new_sim(&sim);
+        let mut scores = Array2::zeros((20, 2));
+        axis.add_to_scores(&mut scores, &mut trial_rng(3, 0));
+        assert_eq!(axis.get_name(), "empirical");
+        let mut posns = Vec::new();
+        axis.push_posn_elements(&mut |x, _| posns.push(x), &vec![0, 1]);
+        assert_eq!(posns[0], -posns[2]);
+        for row in scores.rows() {
+            assert_float_eq!(row[0].max(row[1]), -(0.3125_f64).sqrt(), abs <= 1.0e-12);
+        }
+
+        // The id column isn't a position in the candidates' file.
+        let c: Consideration = toml::from_str(&format!(
+            "[Empirical]\nvoters = {:?}\ncandidates = {:?}",
+            voters, candidates
+        ))
+        .unwrap();
+        assert!(c.validate().is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Positions read by validate are kept without the files.
+        let cloned = empirical.clone();
+        assert!(cloned.validate().is_ok());
+        cloned.new_sim(&sim);
+    }
+}
@@ -0,0 +1,251 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sim::{Sim, SimRng};
+use ndarray::Array2;
+
+use super::{Consideration, ConsiderationSim};
+
+/// How the utilities from several considerations are combined into one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Combine {
+    /// Utilities are summed.
+    #[default]
+    Add,
+    /// Utilities are multiplied. This is most sensible for considerations
+    /// with positive utilities, like Likability, which then scale each other.
+    Multiply,
+    /// The square root of the sum of squares, keeping signs: squares of negative
+    /// utilities are subtracted, and a negative total gives a negative root.
+    /// Issue distances combine this way into one Euclidean distance.
+    Quadrature,
+}
+
+/// Arrays for combining utilities other than by adding them, kept between
+/// elections. They're sized by the first use.
+#[derive(Debug, Default)]
+pub struct CombineScratch {
+    combined: Array2<f64>,
+    part: Array2<f64>,
+}
+
+impl Combine {
+    /// Checks that the considerations can be combined this way. Multiplying
+    /// negative utilities would reverse voters' preferences.
+    pub fn validate(&self, considerations: &[Consideration]) -> Result<(), String> {
+        if *self == Combine::Multiply {
+            if let Some(c) = considerations.iter().find(|c| !c.never_negative()) {
+                return Err(format!(
+                    "Multiply needs considerations whose utilities are never negative, got {:?}",
+                    c
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the combined utilities of every axis to scores.
+    pub fn add_to_scores(
+        &self,
+        axes: &mut [Box<dyn ConsiderationSim>],
+        scores: &mut Array2<f64>,
+        scratch: &mut CombineScratch,
+        rng: &mut SimRng,
+    ) {
+        if *self == Combine::Add {
+            for ax in axes.iter_mut() {
+                ax.add_to_scores(scores, rng);
+            }
+            return;
+        }
+        let identity = match self {
+            Combine::Multiply => 1.0,
+            _ => 0.0,
+        };
+        let CombineScratch { combined, part } = scratch;
+        if combined.dim() != scores.dim() {
+            *combined = Array2::zeros(scores.dim());
+            *part = Array2::zeros(scores.dim());
+        }
+        combined.fill(identity);
+        for ax in axes.iter_mut() {
+            part.fill(0.0);
+            ax.add_to_scores(part, rng);
+            match self {
+                Combine::Multiply => *combined *= &*part,
+                _ => combined.zip_mut_with(part, |c, &u| *c += u * u.abs()),
+            }
+        }
+        if *self == Combine::Quadrature {
+            combined.mapv_inplace(|c| c.signum() * c.abs().sqrt());
+        }
+        *scores += &*combined;
+    }
+}
+
+/// A group of considerations whose utilities are combined by their own rule,
+/// and then combined with the other considerations as one.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Group {
+    #[serde(default)]
+    pub combine: Combine,
+    pub considerations: Vec<Consideration>,
+}
+
+#[derive(Debug)]
+pub struct GroupSim {
+    combine: Combine,
+    members: Vec<Box<dyn ConsiderationSim>>,
+    scratch: CombineScratch,
+}
+
+impl Group {
+    pub fn new_sim(&self, sim: &Sim) -> GroupSim {
+        GroupSim {
+            combine: self.combine,
+            members: self.considerations.iter().map(|c| c.new_sim(sim)).collect(),
+            scratch: CombineScratch::default(),
+        }
+    }
+}
+
+impl ConsiderationSim for GroupSim {
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng) {
+        self.combine
+            .add_to_scores(&mut self.members, scores, &mut self.scratch, rng);
+    }
+
+    fn get_dim(&self) -> usize {
+        self.members.iter().map(|m| m.get_dim()).sum()
+    }
+
+    fn get_name(&self) -> String {
+        let names: Vec<String> = self.members.iter().map(|m| m.get_name()).collect();
+        format!("group_{}", names.join("_"))
+    }
+
+    /// Each candidate's elements are those of every member in turn.
+    fn push_posn_elements(&self, report: &mut dyn FnMut(f64, bool), final_candidates: &Vec<usize>) {
+        let mut rows = vec![Vec::with_capacity(self.get_dim()); final_candidates.len()];
+        for member in self.members.iter() {
+            let mut icand = 0;
+            member.push_posn_elements(
+                &mut |x, next_row| {
+                    rows[icand].push(x);
+                    if next_row {
+                        icand += 1;
+                    }
+                },
+                final_candidates,
+            );
+        }
+        for row in rows.iter() {
+            for (i, &x) in row.iter().enumerate() {
+                report(x, i == row.len() - 1);
+            }
+        }
+    }
+
+    fn num_factions(&self) -> usize {
+        self.members
+            .iter()
+            .map(|m| m.num_factions())
+            .find(|&n| n > 1)
+            .unwrap_or(1)
+    }
+
+    fn salience_spread(&self) -> Option<f64> {
+        self.members.iter().find_map(|m| m.salience_spread())
+    }
+
+    fn voter_faction(&self, icit: usize) -> usize {
+        match self.members.iter().find(|m| m.num_factions() > 1) {
+            Some(m) => m.voter_faction(icit),
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::considerations::Likability;
+    use crate::sim::trial_rng;
+
+    /// Gives every voter the same utility for every candidate.
+    #[derive(Debug)]
+    struct Constant(f64);
+
+    impl ConsiderationSim for Constant {
+        fn add_to_scores(&mut self, scores: &mut Array2<f64>, _rng: &mut SimRng) {
+            *scores += self.0;
+        }
+        fn get_dim(&self) -> usize {
+            1
+        }
+        fn get_name(&self) -> String {
+            "constant".to_string()
+        }
+        fn push_posn_elements(
+            &self,
+            report: &mut dyn FnMut(f64, bool),
+            final_candidates: &Vec<usize>,
+        ) {
+            for _ in final_candidates.iter() {
+                report(self.0, true);
+            }
+        }
+    }
+
+    fn combined(combine: Combine, utilities: &[f64]) -> f64 {
+        let mut axes: Vec<Box<dyn ConsiderationSim>> = utilities
+            .iter()
+            .map(|&u| Box::new(Constant(u)) as Box<dyn ConsiderationSim>)
+            .collect();
+        let mut scores = Array2::from_elem((2, 3), 1.0);
+        let mut scratch = CombineScratch::default();
+        combine.add_to_scores(&mut axes, &mut scores, &mut scratch, &mut trial_rng(0, 0));
+        scores[(1, 2)] - 1.0
+    }
+
+    #[test]
+    fn test_combine() {
+        assert_eq!(combined(Combine::Add, &[-3.0, -4.0]), -7.0);
+        assert_eq!(combined(Combine::Multiply, &[2.0, 3.0]), 6.0);
+        assert_eq!(combined(Combine::Quadrature, &[-3.0, -4.0]), -5.0);
+        assert_eq!(combined(Combine::Quadrature, &[3.0, 4.0]), 5.0);
+        assert_eq!(combined(Combine::Quadrature, &[5.0, -4.0]), 3.0);
+
+        let likability = Consideration::Likability(Likability { mean: 1.0 });
+        let issues = |utility_fn: &str| -> Consideration {
+            toml::from_str(&format!(
+                "[Issues]\nissues = [{{ sigma = 1.0, halfcsep = 0.0 }}]\nutility_fn = {}",
+                utility_fn
+            ))
+            .unwrap()
+        };
+        // Linear utilities are negative, and Gaussian ones positive.
+        let linear = [likability.clone(), issues("\"Linear\"")];
+        assert!(Combine::Multiply.validate(&linear).is_err());
+        assert!(Combine::Add.validate(&linear).is_ok());
+        let gaussian = [likability, issues("{ Gaussian = { width = 1.0 } }")];
+        assert!(Combine::Multiply.validate(&gaussian).is_ok());
+    }
+
+    #[test]
+    fn test_group_positions() {
+        let group = GroupSim {
+            combine: Combine::Quadrature,
+            members: vec![Box::new(Constant(1.0)), Box::new(Constant(2.0))],
+            scratch: CombineScratch::default(),
+        };
+        let mut posns = Vec::new();
+        group.push_posn_elements(&mut |x, next_row| posns.push((x, next_row)), &vec![0, 1]);
+        assert_eq!(
+            posns,
+            vec![(1.0, false), (2.0, true), (1.0, false), (2.0, true)]
+        );
+        assert_eq!(group.get_dim(), 2);
+    }
+}
@@ -1,10 +1,9 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sim::Sim;
+use crate::sim::{Sim, SimRng};
 use ndarray::Array2;
 use rand::distr::StandardUniform;
-use rand::rngs::ThreadRng;
 use rand::Rng;
 
 use super::ConsiderationSim;
@@ -77,7 +76,7 @@ impl Irrational {
 
 impl ConsiderationSim for IrrationalSim {
     #[allow(unused_variables)]
-    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut ThreadRng) {
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng) {
         let (ncit, ncand) = scores.dim();
         if self.p.uses_camps() {
             let (ncamps, ncand_from_self) = self.camp_scores.dim();
@@ -113,4 +112,16 @@ impl ConsiderationSim for IrrationalSim {
             report(f64::NAN, true);
         }
     }
+
+    fn num_factions(&self) -> usize {
+        if self.p.uses_camps() {
+            self.p.camps
+        } else {
+            1
+        }
+    }
+
+    fn voter_faction(&self, icit: usize) -> usize {
+        icit % self.num_factions()
+    }
 }
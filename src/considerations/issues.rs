@@ -1,12 +1,14 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use super::ConsiderationSim;
-use crate::sim::Sim;
-use ndarray::Array2;
-use rand::rngs::ThreadRng;
+use crate::cov_matrix::cholesky;
+use crate::sim::{Sim, SimRng};
+use ndarray::{s, Array1, Array2, Axis};
 use rand::Rng;
-use rand_distr::StandardNormal;
+use rand_distr::{Distribution, Gamma, StandardNormal};
 
 const SQRT_3: f64 = 1.732050807568877293527446341505872367_f64; // borrowed from nightly
 
@@ -25,6 +27,8 @@ pub struct Issue {
     /// by -halfcsep, and half by +halfcsep.
     pub halfcsep: f64,
     /// Adds a polarization gap to the citizens. Similar to halfcsep.
+    /// The first issue with a nonzero gap divides voters into two factions,
+    /// 0 for the negative side and 1 for the positive side.
     pub halfvsep: Option<f64>,
     /// If uniform=true, citizen and candidate positons are drawn according
     /// to a uniform distribution instead of normal. But the standard
@@ -40,6 +44,453 @@ pub struct Issue {
     pub horizon: f64,
 }
 
+/// The Issues consideration: either just a list of issues, or a table that
+/// also chooses how distance in issue space becomes utility.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Issues {
+    /// Linear utility of Euclidean distance
+    List(Vec<Issue>),
+    Space(IssueSpace),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IssueSpace {
+    pub issues: Vec<Issue>,
+    #[serde(default)]
+    pub utility_fn: UtilityFn,
+    #[serde(default)]
+    pub metric: Metric,
+    /// Correlations between issues, scaled by each issue's sigma. Positions are
+    /// then drawn from a multivariate normal instead of issue by issue.
+    pub correlation: Option<Vec<Vec<f64>>>,
+    /// Or the full covariance between issues, which replaces their sigmas
+    pub covariance: Option<Vec<Vec<f64>>>,
+    /// How much each voter cares about each issue. Without it, all issues
+    /// count equally for everyone.
+    pub salience: Option<Salience>,
+    /// Draws voter positions from a mixture of clusters, instead of from each
+    /// issue's own distribution. Each voter's cluster is their faction.
+    pub voter_clusters: Option<Vec<Cluster>>,
+    /// Draws candidate positions from a mixture of clusters
+    pub cand_clusters: Option<Vec<Cluster>>,
+    /// Nominates candidates from each election's voters, instead of drawing
+    /// their positions independently
+    pub nomination: Option<Nomination>,
+    /// Gives candidates a likability that depends on their distance from the
+    /// voters' centroid
+    pub valence: Option<Valence>,
+}
+
+/// A candidate's valence is base + slope * (distance from the voters' centroid),
+/// plus normal noise, and adds to every voter's utility for them. A negative
+/// slope makes moderates more likable; a positive one favors extremists.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Valence {
+    /// Valence of a candidate at the centroid
+    #[serde(default)]
+    pub base: f64,
+    pub slope: f64,
+    /// Standard deviation of each candidate's noise
+    #[serde(default)]
+    pub noise: f64,
+}
+
+/// Which of an election's voters may become its candidates. Candidates take
+/// the positions of distinct voters.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum Nomination {
+    /// Any voter, with equal chances
+    Voters,
+    /// Only voters at least as far from the voters' centroid as this quantile
+    /// of voters, so 0.8 nominates from the outermost fifth
+    Extremes { quantile: f64 },
+    /// Any voter, with chances proportional to their distance from the voters'
+    /// centroid raised to intensity. Activists tend to be more extreme.
+    Activists { intensity: f64 },
+}
+
+/// Distributions of voters' salience weights, which scale each issue's
+/// contribution to distance. Weights average 1 across issues.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum Salience {
+    /// Weights from a Dirichlet distribution with one concentration per issue.
+    /// Small concentrations make each voter care mostly about one issue, and
+    /// large ones make voters weigh issues nearly equally.
+    Dirichlet { alpha: Vec<f64> },
+}
+
+impl Salience {
+    pub fn validate(&self, nissues: usize) -> Result<(), String> {
+        let Salience::Dirichlet { alpha } = self;
+        if alpha.len() != nissues || alpha.iter().any(|&a| a.is_nan() || a <= 0.0) {
+            return Err(format!(
+                "Salience needs a positive alpha for each of {} issues, got {:?}",
+                nissues, alpha
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A multivariate normal lump of positions across all issues.
+/// Give either sigma or covariance.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cluster {
+    /// The relative probability of drawing from this cluster
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Position on each issue
+    pub center: Vec<f64>,
+    /// Standard deviation along each issue, drawn independently
+    pub sigma: Option<Vec<f64>>,
+    /// Full covariance matrix between issues
+    pub covariance: Option<Vec<Vec<f64>>>,
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// How a voter's utility for a candidate falls off with their distance d.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum UtilityFn {
+    /// -d
+    #[default]
+    Linear,
+    /// -d^2
+    Quadratic,
+    /// exp(-d^2 / (2 width^2)), so distant candidates all look about equally bad.
+    Gaussian { width: f64 },
+    /// 1 / (1 + (d / width)^2), with heavier tails than Gaussian.
+    Cauchy { width: f64 },
+}
+
+/// How distances along each issue combine into one distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Metric {
+    /// The square root of the sum of squares
+    #[default]
+    Euclidean,
+    /// The sum of absolute values
+    CityBlock,
+}
+
+impl UtilityFn {
+    fn utility(&self, dist: f64) -> f64 {
+        match *self {
+            UtilityFn::Linear => -dist,
+            UtilityFn::Quadratic => -dist * dist,
+            UtilityFn::Gaussian { width } => (-0.5 * (dist / width).powi(2)).exp(),
+            UtilityFn::Cauchy { width } => 1.0 / (1.0 + (dist / width).powi(2)),
+        }
+    }
+
+    /// Gaussian and Cauchy utilities are positive, and the others negative.
+    pub fn never_negative(&self) -> bool {
+        matches!(self, UtilityFn::Gaussian { .. } | UtilityFn::Cauchy { .. })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            UtilityFn::Gaussian { width } | UtilityFn::Cauchy { width }
+                if width.is_nan() || width <= 0.0 =>
+            {
+                Err(format!("Utility width must be positive, got {}", width))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Issues {
+    /// The number of voter factions, as IssuesSim::num_factions will find:
+    /// voter clusters if there are several, or else the two sides of the first
+    /// polarized issue.
+    pub fn num_factions(&self) -> usize {
+        if let Issues::Space(IssueSpace {
+            voter_clusters: Some(clusters),
+            ..
+        }) = self
+        {
+            return clusters.len().max(1);
+        }
+        if self.issues().iter().any(|i| i.voter_sep() != 0.0) {
+            2
+        } else {
+            1
+        }
+    }
+
+    pub fn issues(&self) -> &[Issue] {
+        match self {
+            Issues::List(issues) => issues,
+            Issues::Space(space) => &space.issues,
+        }
+    }
+
+    pub fn new_sim(&self, sim: &Sim) -> IssuesSim {
+        let mut issues_sim = new_issues_sim(self.issues().to_vec(), sim);
+        if let Issues::Space(space) = self {
+            issues_sim.set_distance(space.utility_fn, space.metric, space.salience.as_ref());
+            issues_sim.factor = space
+                .joint_covariance()
+                .expect("Invalid issue covariance")
+                .map(|cov| cholesky(&cov).expect("Invalid issue covariance"));
+            issues_sim.voter_mixture = space.voter_clusters.as_deref().map(Mixture::new);
+            issues_sim.cand_mixture = space.cand_clusters.as_deref().map(Mixture::new);
+            issues_sim.nomination = space.nomination;
+            issues_sim.valence = space.valence;
+            if let Some(mixture) = &issues_sim.voter_mixture {
+                issues_sim.faction_issue = None;
+                issues_sim.nclusters = mixture.centers.len();
+            }
+        }
+        issues_sim
+    }
+
+    /// The effective number of dimensions of the voter population: the
+    /// participation ratio (sum of eigenvalues)^2 / (sum of squared eigenvalues)
+    /// of its covariance. It's n for n independent issues of equal spread, and
+    /// close to 1 when one direction dominates.
+    pub fn effective_dim(&self) -> f64 {
+        let cov = match self {
+            Issues::List(issues) => Array2::from_diag(&Array1::from_iter(
+                issues
+                    .iter()
+                    .map(|i| i.sigma.powi(2) + i.voter_sep().powi(2)),
+            )),
+            Issues::Space(space) => space.voter_covariance(),
+        };
+        let trace = cov.diag().sum();
+        let trace_sq: f64 = cov.iter().map(|c| c * c).sum();
+        if trace_sq > 0.0 {
+            trace * trace / trace_sq
+        } else {
+            0.0
+        }
+    }
+
+    /// Checks that clusters match the issues and have valid covariances.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Issues::Space(space) = self {
+            let nissues = space.issues.len();
+            space.utility_fn.validate()?;
+            if let Some(salience) = &space.salience {
+                salience.validate(nissues)?;
+            }
+            if let Some(valence) = &space.valence {
+                if valence.noise.is_nan() || valence.noise < 0.0 {
+                    return Err(format!(
+                        "Valence noise can't be negative, got {}",
+                        valence.noise
+                    ));
+                }
+            }
+            match space.nomination {
+                Some(_) if space.cand_clusters.is_some() => {
+                    return Err(
+                        "Candidates can be nominated from voters or drawn from clusters, not both"
+                            .to_string(),
+                    );
+                }
+                Some(Nomination::Extremes { quantile }) if !(0.0..1.0).contains(&quantile) => {
+                    return Err(format!(
+                        "Nomination quantile must be at least 0 and less than 1, got {}",
+                        quantile
+                    ));
+                }
+                Some(Nomination::Activists { intensity })
+                    if intensity.is_nan() || intensity < 0.0 =>
+                {
+                    return Err(format!(
+                        "Nomination intensity can't be negative, got {}",
+                        intensity
+                    ));
+                }
+                _ => (),
+            }
+            if let Some(cov) = space.joint_covariance()? {
+                cholesky(&cov)?;
+                if space.issues.iter().any(|i| i.uniform) {
+                    return Err("Correlated issues are drawn from normal distributions, \
+                        so they can't be uniform"
+                        .to_string());
+                }
+            }
+            for clusters in [&space.voter_clusters, &space.cand_clusters]
+                .into_iter()
+                .flatten()
+            {
+                if clusters.is_empty() || clusters.iter().all(|c| c.weight <= 0.0) {
+                    return Err("Issue clusters need at least one positive weight".to_string());
+                }
+                for cluster in clusters.iter() {
+                    cluster.validate(nissues)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IssueSpace {
+    /// The joint covariance of positions around their polarization offsets,
+    /// if issues are correlated.
+    fn joint_covariance(&self) -> Result<Option<Array2<f64>>, String> {
+        let n = self.issues.len();
+        let matrix = |m: &Vec<Vec<f64>>, name: &str| {
+            if m.len() != n || m.iter().any(|row| row.len() != n) {
+                return Err(format!("The {} matrix must be {} x {}", name, n, n));
+            }
+            Ok(Array2::from_shape_fn((n, n), |(i, j)| m[i][j]))
+        };
+        match (&self.correlation, &self.covariance) {
+            (Some(_), Some(_)) => Err("Give issues a correlation or a covariance, not both".into()),
+            (Some(rho), None) => {
+                let rho = matrix(rho, "correlation")?;
+                if rho.diag().iter().any(|&r| r != 1.0) {
+                    return Err("Correlation matrix diagonal elements must be 1".into());
+                }
+                Ok(Some(Array2::from_shape_fn((n, n), |(i, j)| {
+                    rho[(i, j)] * self.issues[i].sigma * self.issues[j].sigma
+                })))
+            }
+            (None, Some(cov)) => matrix(cov, "covariance").map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The covariance of voter positions across the whole population
+    fn voter_covariance(&self) -> Array2<f64> {
+        let n = self.issues.len();
+        if let Some(clusters) = &self.voter_clusters {
+            // Mixture covariance: within-cluster plus between-cluster spread
+            let total: f64 = clusters.iter().map(|c| c.weight).sum();
+            let mut mean = vec![0.0; n];
+            let mut cov = Array2::zeros((n, n));
+            for cluster in clusters.iter() {
+                let w = cluster.weight / total;
+                let factor = cluster.cholesky(n).expect("Invalid cluster");
+                cov = cov + w * factor.dot(&factor.t());
+                for i in 0..n {
+                    mean[i] += w * cluster.center[i];
+                    for j in 0..n {
+                        cov[(i, j)] += w * cluster.center[i] * cluster.center[j];
+                    }
+                }
+            }
+            for i in 0..n {
+                for j in 0..n {
+                    cov[(i, j)] -= mean[i] * mean[j];
+                }
+            }
+            return cov;
+        }
+        let mut cov = self
+            .joint_covariance()
+            .expect("Invalid issue covariance")
+            .unwrap_or_else(|| {
+                Array2::from_diag(&Array1::from_iter(
+                    self.issues.iter().map(|i| i.sigma.powi(2)),
+                ))
+            });
+        // Polarized voters sit at +/- the separation with equal odds.
+        for (i, issue) in self.issues.iter().enumerate() {
+            cov[(i, i)] += issue.voter_sep().powi(2);
+        }
+        cov
+    }
+}
+
+impl Cluster {
+    fn validate(&self, nissues: usize) -> Result<(), String> {
+        if self.weight < 0.0 {
+            return Err(format!("Cluster weight is negative: {}", self.weight));
+        }
+        if self.center.len() != nissues {
+            return Err(format!(
+                "Cluster center {:?} needs one value per issue ({})",
+                self.center, nissues
+            ));
+        }
+        self.cholesky(nissues).map(|_| ())
+    }
+
+    /// The lower-triangular factor of the cluster's covariance
+    fn cholesky(&self, nissues: usize) -> Result<Array2<f64>, String> {
+        match (&self.sigma, &self.covariance) {
+            (Some(sigma), None) if sigma.len() == nissues => {
+                if sigma.iter().any(|&s| s < 0.0) {
+                    return Err(format!("Cluster sigma is negative: {:?}", sigma));
+                }
+                Ok(Array2::from_diag(&ndarray::arr1(sigma)))
+            }
+            (None, Some(cov)) if cov.len() == nissues && cov.iter().all(|r| r.len() == nissues) => {
+                cholesky(&Array2::from_shape_fn((nissues, nissues), |(i, j)| {
+                    cov[i][j]
+                }))
+            }
+            (Some(_), Some(_)) | (None, None) => {
+                Err("A cluster needs either sigma or covariance".to_string())
+            }
+            _ => Err(format!("Cluster spreads need {} issues", nissues)),
+        }
+    }
+}
+
+/// A mixture of clusters, ready to draw positions from.
+#[derive(Debug)]
+struct Mixture {
+    cum_weights: Vec<f64>,
+    centers: Vec<Vec<f64>>,
+    factors: Vec<Array2<f64>>,
+}
+
+impl Mixture {
+    fn new(clusters: &[Cluster]) -> Mixture {
+        let nissues = clusters[0].center.len();
+        let mut total = 0.0;
+        Mixture {
+            cum_weights: clusters
+                .iter()
+                .map(|c| {
+                    total += c.weight;
+                    total
+                })
+                .collect(),
+            centers: clusters.iter().map(|c| c.center.clone()).collect(),
+            factors: clusters
+                .iter()
+                .map(|c| c.cholesky(nissues).expect("Invalid cluster"))
+                .collect(),
+        }
+    }
+
+    /// Fills position with a draw, and returns the cluster it came from.
+    fn sample(&self, rng: &mut SimRng, position: &mut [f64]) -> usize {
+        let x = rng.random::<f64>() * self.cum_weights.last().unwrap();
+        let icluster = self
+            .cum_weights
+            .iter()
+            .position(|&w| x < w)
+            .unwrap_or(self.cum_weights.len() - 1);
+        let z: Vec<f64> = (0..position.len())
+            .map(|_| rng.sample(StandardNormal))
+            .collect();
+        let factor = &self.factors[icluster];
+        for (i, p) in position.iter_mut().enumerate() {
+            *p = self.centers[icluster][i] + (0..=i).map(|k| factor[(i, k)] * z[k]).sum::<f64>();
+        }
+        icluster
+    }
+}
+
 fn default_false() -> bool {
     false
 }
@@ -51,52 +502,320 @@ fn default_big() -> f64 {
 
 #[derive(Debug)]
 pub struct IssuesSim {
+    name: &'static str,
     issues: Vec<Issue>,
+    utility_fn: UtilityFn,
+    metric: Metric,
     cand_position: Array2<f64>,
+    cands_fixed: bool, // Candidates keep their positions instead of being drawn
+    voter_offset: Vec<f64>,
     horizon_sq: Vec<f64>,
+    faction_issue: Option<usize>, // The first issue with a voter polarization gap
+    factor: Option<Array2<f64>>,  // Cholesky factor of the joint covariance
+    salience: Option<Vec<(Gamma<f64>, f64)>>, // Gamma(alpha + 1) and alpha for each issue
+    weights: Vec<f64>,            // The current voter's salience weights
+    salience_spread: f64,
+    voter_mixture: Option<Mixture>,
+    cand_mixture: Option<Mixture>,
+    voter_rows: Option<Arc<Array2<f64>>>, // Positions to resample voters from
+    cand_rows: Option<Arc<Array2<f64>>>,  // Positions to draw candidates from
+    nomination: Option<Nomination>,
+    valence: Option<Valence>,
+    voter_position: Array2<f64>, // (cit, issue)
+    nclusters: usize,            // Voter clusters
+    cit_faction: Vec<usize>,
 }
 
 pub fn new_issues_sim(issues: Vec<Issue>, sim: &Sim) -> IssuesSim {
     let num_issues = issues.len();
     let horizon_sq = issues.iter().map(|i| i.horizon.powi(2)).collect();
+    let faction_issue = issues.iter().position(|i| i.voter_sep() != 0.0);
     IssuesSim {
+        name: "issues",
         issues,
+        utility_fn: UtilityFn::default(),
+        metric: Metric::default(),
         cand_position: Array2::zeros((sim.ncand, num_issues)),
+        cands_fixed: false,
+        voter_offset: vec![0.0; num_issues],
         horizon_sq,
+        faction_issue,
+        factor: None,
+        salience: None,
+        weights: vec![1.0; num_issues],
+        salience_spread: 0.0,
+        voter_mixture: None,
+        cand_mixture: None,
+        voter_rows: None,
+        cand_rows: None,
+        nomination: None,
+        valence: None,
+        voter_position: Array2::zeros((sim.ncit, num_issues)),
+        nclusters: 0,
+        cit_faction: vec![0; sim.ncit],
+    }
+}
+
+impl IssuesSim {
+    /// Places the candidates at fixed positions, one row per candidate,
+    /// instead of drawing new positions for every election.
+    pub fn fix_candidates(&mut self, positions: &Array2<f64>) {
+        self.cand_position.assign(positions);
+        self.cands_fixed = true;
+    }
+
+    /// Shifts every voter's position by offset.
+    pub fn set_voter_offset(&mut self, offset: &[f64]) {
+        self.voter_offset.copy_from_slice(offset);
+    }
+
+    /// Chooses how distance becomes utility.
+    pub(super) fn set_distance(
+        &mut self,
+        utility_fn: UtilityFn,
+        metric: Metric,
+        salience: Option<&Salience>,
+    ) {
+        self.utility_fn = utility_fn;
+        self.metric = metric;
+        self.salience = salience.map(|Salience::Dirichlet { alpha }| {
+            alpha
+                .iter()
+                .map(|&a| (Gamma::new(a + 1.0, 1.0).expect("Invalid salience alpha"), a))
+                .collect()
+        });
+    }
+
+    /// Draws voters from the rows of voters, with replacement, and candidates
+    /// from the rows of candidates, without replacement while there are enough.
+    pub(super) fn resample_rows(
+        &mut self,
+        name: &'static str,
+        voters: Arc<Array2<f64>>,
+        candidates: Arc<Array2<f64>>,
+    ) {
+        self.name = name;
+        self.voter_rows = Some(voters);
+        self.cand_rows = Some(candidates);
+    }
+}
+
+impl IssuesSim {
+    /// Draws a position from the issues' own distributions, correlated if there
+    /// is a factor of the joint covariance. Returns whether the position is on
+    /// the negative side of the faction issue's polarization gap.
+    fn draw_from_issues(&self, rng: &mut SimRng, is_voter: bool, position: &mut [f64]) -> bool {
+        let correlated: Option<Vec<f64>> = self.factor.as_ref().map(|factor| {
+            let z: Vec<f64> = (0..position.len())
+                .map(|_| rng.sample(StandardNormal))
+                .collect();
+            (0..position.len())
+                .map(|i| (0..=i).map(|k| factor[(i, k)] * z[k]).sum())
+                .collect()
+        });
+        let mut negative_side = false;
+        for (ipos, issue) in self.issues.iter().enumerate() {
+            let side = rng.random::<bool>();
+            position[ipos] = match &correlated {
+                Some(x) => x[ipos] + issue.separation(is_voter, side),
+                None => issue.gen_value(rng, is_voter, side),
+            };
+            if self.faction_issue == Some(ipos) {
+                negative_side = side;
+            }
+        }
+        negative_side
+    }
+
+    fn draw_candidates(&mut self, rng: &mut SimRng, ncand: usize) {
+        if let Some(rows) = &self.cand_rows {
+            let nrows = rows.nrows();
+            let distinct = rand::seq::index::sample(rng, nrows, ncand.min(nrows));
+            for i in 0..ncand {
+                let irow = if i < distinct.len() {
+                    distinct.index(i)
+                } else {
+                    rng.random_range(0..nrows)
+                };
+                self.cand_position.row_mut(i).assign(&rows.row(irow));
+            }
+            return;
+        }
+        let mut cand_position = vec![0.0; self.issues.len()];
+        for i in 0..ncand {
+            if let Some(mixture) = &self.cand_mixture {
+                mixture.sample(rng, &mut cand_position);
+            } else {
+                self.draw_from_issues(rng, false, &mut cand_position);
+            }
+            for (ipos, &x) in cand_position.iter().enumerate() {
+                self.cand_position[(i, ipos)] = x;
+            }
+        }
+    }
+
+    /// Draws voter j's position, and their faction.
+    fn draw_voter(&mut self, rng: &mut SimRng, j: usize, position: &mut [f64]) {
+        if let Some(rows) = &self.voter_rows {
+            let irow = rng.random_range(0..rows.nrows());
+            for (p, &x) in position.iter_mut().zip(rows.row(irow).iter()) {
+                *p = x;
+            }
+        } else if let Some(mixture) = &self.voter_mixture {
+            self.cit_faction[j] = mixture.sample(rng, position);
+        } else if self.draw_from_issues(rng, true, position) {
+            self.cit_faction[j] = 0;
+        } else {
+            self.cit_faction[j] = 1;
+        }
+        for (p, offset) in position.iter_mut().zip(self.voter_offset.iter()) {
+            *p += offset;
+        }
+    }
+
+    /// Places the candidates at the positions of voters chosen by the nomination rule.
+    fn nominate(&mut self, rng: &mut SimRng, ncit: usize, ncand: usize) {
+        let voters = self.voter_position.slice(s![..ncit, ..]);
+        let centroid = voters.mean_axis(Axis(0)).unwrap();
+        let dist: Vec<f64> = voters
+            .rows()
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(centroid.iter())
+                    .map(|(x, c)| (x - c).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+        let mut chances: Vec<f64> = match self.nomination.unwrap() {
+            Nomination::Voters => vec![1.0; ncit],
+            Nomination::Extremes { quantile } => {
+                let mut sorted = dist.clone();
+                sorted.sort_by(f64::total_cmp);
+                // Keep at least as many voters as candidates eligible.
+                let ithreshold =
+                    ((quantile * ncit as f64) as usize).min(ncit.saturating_sub(ncand));
+                dist.iter()
+                    .map(|&d| if d >= sorted[ithreshold] { 1.0 } else { 0.0 })
+                    .collect()
+            }
+            Nomination::Activists { intensity } => dist.iter().map(|d| d.powf(intensity)).collect(),
+        };
+        if chances.iter().all(|&c| c <= 0.0) {
+            // Every voter is at the centroid.
+            chances.fill(1.0);
+        }
+        let eligible = chances.iter().filter(|&&c| c > 0.0).count();
+        let nominees =
+            rand::seq::index::sample_weighted(rng, ncit, |i| chances[i], ncand.min(eligible))
+                .expect("Invalid nomination chances");
+        for i in 0..ncand {
+            // Voters are renominated only when there are more candidates than voters.
+            let icit = if i < nominees.len() {
+                nominees.index(i)
+            } else {
+                nominees.index(rng.random_range(0..nominees.len()))
+            };
+            self.cand_position
+                .row_mut(i)
+                .assign(&self.voter_position.row(icit));
+        }
     }
 }
 
 impl ConsiderationSim for IssuesSim {
-    fn add_to_scores(&mut self, scores: &mut Array2<f64>, mut rng: &mut ThreadRng) {
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng) {
         let (ncit, ncand) = scores.dim();
         // All citizens are the same in this regard.
         // Or at least we assume there are enough citizens that every representative
         // group in position-space spans all degrees of likability alignment.
         let npos = self.issues.len();
-        for i in 0..ncand {
-            for (ipos, issue) in self.issues.iter().enumerate() {
-                self.cand_position[(i, ipos)] = issue.gen_value(&mut rng, false);
+        let nominated = self.nomination.is_some() && !self.cands_fixed;
+        if !nominated && !self.cands_fixed {
+            self.draw_candidates(rng, ncand);
+        }
+        let mut cit_position = vec![0.0; npos];
+        if nominated {
+            // Every voter needs a position before any can be nominated.
+            for j in 0..ncit {
+                self.draw_voter(rng, j, &mut cit_position);
+                for (p, &x) in cit_position.iter().enumerate() {
+                    self.voter_position[(j, p)] = x;
+                }
             }
+            self.nominate(rng, ncit, ncand);
         }
         log::debug!("Candidate positions: {:?}", self.cand_position);
-        let mut cit_position = vec![0.0; npos];
+        let mut spread_total = 0.0;
+        let mut centroid = vec![0.0; npos];
         for j in 0..ncit {
-            for (ipos, issue) in self.issues.iter().enumerate() {
-                cit_position[ipos] = issue.gen_value(&mut rng, true);
+            if nominated {
+                for (p, x) in cit_position.iter_mut().enumerate() {
+                    *x = self.voter_position[(j, p)];
+                }
+            } else {
+                self.draw_voter(rng, j, &mut cit_position);
+            }
+            if let Some(gammas) = &self.salience {
+                // Dirichlet draws are normalized Gamma(alpha) draws, which underflow
+                // to 0 for tiny alphas. So they're drawn as logs, using the fact
+                // that Gamma(alpha + 1) * U^(1 / alpha) is Gamma(alpha) for uniform U.
+                for (w, (gamma, alpha)) in self.weights.iter_mut().zip(gammas.iter()) {
+                    let u: f64 = rng.random();
+                    *w = gamma.sample(rng).ln() + (1.0 - u).ln() / alpha;
+                }
+                let max = self.weights.iter().copied().fold(f64::MIN, f64::max);
+                for w in self.weights.iter_mut() {
+                    *w = (*w - max).exp();
+                }
+                let total: f64 = self.weights.iter().sum();
+                let mut var = 0.0;
+                for w in self.weights.iter_mut() {
+                    *w *= npos as f64 / total;
+                    var += (*w - 1.0).powi(2) / npos as f64;
+                }
+                spread_total += var.sqrt();
             }
             log::debug!("cit {}: {:?}", j, cit_position);
+            for (c, &x) in centroid.iter_mut().zip(cit_position.iter()) {
+                *c += x / ncit as f64;
+            }
             for i in 0..ncand {
-                let mut distsq = 0.0;
-                for p in 0..npos {
-                    let diff = self.cand_position[(i, p)] - cit_position[p];
-                    let diffsq = diff * diff;
-                    if diffsq < self.horizon_sq[p] {
-                        distsq += diffsq;
-                    } else {
-                        distsq += self.horizon_sq[p];
+                let dist = match self.metric {
+                    Metric::Euclidean => {
+                        let mut distsq = 0.0;
+                        for p in 0..npos {
+                            let diff = self.cand_position[(i, p)] - cit_position[p];
+                            let diffsq = diff * diff;
+                            if diffsq < self.horizon_sq[p] {
+                                distsq += self.weights[p] * diffsq;
+                            } else {
+                                distsq += self.weights[p] * self.horizon_sq[p];
+                            }
+                        }
+                        distsq.sqrt()
                     }
-                }
-                *scores.get_mut((j, i)).unwrap() += -distsq.sqrt();
+                    Metric::CityBlock => (0..npos)
+                        .map(|p| {
+                            let diff = (self.cand_position[(i, p)] - cit_position[p]).abs();
+                            self.weights[p] * diff.min(self.issues[p].horizon)
+                        })
+                        .sum(),
+                };
+                *scores.get_mut((j, i)).unwrap() += self.utility_fn.utility(dist);
+            }
+        }
+        self.salience_spread = spread_total / ncit as f64;
+        if let Some(valence) = self.valence {
+            for i in 0..ncand {
+                let dist = (0..npos)
+                    .map(|p| (self.cand_position[(i, p)] - centroid[p]).powi(2))
+                    .sum::<f64>()
+                    .sqrt();
+                let noise: f64 = rng.sample(StandardNormal);
+                let cand_valence = valence.base + valence.slope * dist + valence.noise * noise;
+                scores.column_mut(i).mapv_inplace(|u| u + cand_valence);
             }
         }
     }
@@ -106,7 +825,7 @@ impl ConsiderationSim for IssuesSim {
     }
 
     fn get_name(&self) -> String {
-        "issues".to_string()
+        self.name.to_string()
     }
 
     fn push_posn_elements(&self, report: &mut dyn FnMut(f64, bool), final_candidates: &Vec<usize>) {
@@ -117,21 +836,64 @@ impl ConsiderationSim for IssuesSim {
             }
         }
     }
+
+    fn num_factions(&self) -> usize {
+        if self.nclusters > 1 {
+            self.nclusters
+        } else if self.faction_issue.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn voter_faction(&self, icit: usize) -> usize {
+        self.cit_faction[icit]
+    }
+
+    fn salience_spread(&self) -> Option<f64> {
+        self.salience.as_ref().map(|_| self.salience_spread)
+    }
 }
 
 impl Issue {
-    fn gen_value<R: Rng>(&self, rng: &mut R, is_voter: bool) -> f64 {
-        let mut sep = if is_voter {
-            match self.halfvsep {
-                Some(s) => s,
-                None => self.halfcsep,
-            }
+    /// A unit issue without polarization or horizon, for positions that don't
+    /// come from the issue's own distribution
+    pub(super) fn plain() -> Issue {
+        Issue {
+            sigma: 1.0,
+            halfcsep: 0.0,
+            halfvsep: None,
+            uniform: false,
+            horizon: default_big(),
+        }
+    }
+
+    fn voter_sep(&self) -> f64 {
+        match self.halfvsep {
+            Some(s) => s,
+            None => self.halfcsep,
+        }
+    }
+
+    /// The offset of a voter's or candidate's position on the given side of the
+    /// polarization gap
+    fn separation(&self, is_voter: bool, negative_side: bool) -> f64 {
+        let sep = if is_voter {
+            self.voter_sep()
         } else {
             self.halfcsep
         };
-        if rng.random::<bool>() {
-            sep = -sep;
+        if negative_side {
+            -sep
+        } else {
+            sep
         }
+    }
+
+    /// Draws a position on the negative side of the polarization gap if negative_side is set.
+    fn gen_value<R: Rng>(&self, rng: &mut R, is_voter: bool, negative_side: bool) -> f64 {
+        let sep = self.separation(is_voter, negative_side);
         if self.uniform {
             rng.random_range(-SQRT_3..=SQRT_3) * self.sigma + sep
         } else {
@@ -140,3 +902,241 @@ impl Issue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::considerations::Consideration;
+    use crate::sim::trial_rng;
+
+    /// The utility for a voter at (3, 4) of a candidate at the origin.
+    fn utility(config: &str) -> f64 {
+        let consideration: Consideration = toml::from_str(config).unwrap();
+        let issues = match consideration {
+            Consideration::Issues(issues) => issues,
+            _ => panic!("Expected Issues"),
+        };
+        let sim = Sim::new(1, 1);
+        let mut issues_sim = issues.new_sim(&sim);
+        issues_sim.fix_candidates(&Array2::zeros((1, 2)));
+        issues_sim.set_voter_offset(&[3.0, 4.0]);
+        let mut scores = Array2::zeros((1, 1));
+        issues_sim.add_to_scores(&mut scores, &mut trial_rng(0, 0));
+        scores[(0, 0)]
+    }
+
+    const ISSUES: &str = "[{ sigma = 0.0, halfcsep = 0.0 }, { sigma = 0.0, halfcsep = 0.0 }]";
+
+    #[test]
+    fn test_utility_fns() {
+        let space = |options: &str| format!("Issues = {{ issues = {}{} }}", ISSUES, options);
+        assert_eq!(utility(&format!("Issues = {}", ISSUES)), -5.0);
+        assert_eq!(utility(&space("")), -5.0);
+        assert_eq!(utility(&space(", metric = \"CityBlock\"")), -7.0);
+        assert_eq!(utility(&space(", utility_fn = \"Quadratic\"")), -25.0);
+        assert_eq!(
+            utility(&space(", utility_fn = { Gaussian = { width = 5.0 } }")),
+            (-0.5f64).exp()
+        );
+        assert_eq!(
+            utility(&space(", utility_fn = { Cauchy = { width = 5.0 } }")),
+            0.5
+        );
+        for utility_fn in [
+            "{ Gaussian = { width = 0.0 } }",
+            "{ Cauchy = { width = -1.0 } }",
+        ] {
+            let c: Consideration =
+                toml::from_str(&space(&format!(", utility_fn = {}", utility_fn))).unwrap();
+            assert!(c.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_clusters() {
+        let consideration: Consideration = toml::from_str(&format!(
+            r#"
+            [Issues]
+            issues = {}
+            voter_clusters = [
+                {{ weight = 3.0, center = [-2.0, 0.0], sigma = [0.0, 0.0] }},
+                {{ weight = 1.0, center = [2.0, 1.0], covariance = [[0.0, 0.0], [0.0, 0.0]] }},
+            ]
+            cand_clusters = [{{ center = [5.0, 5.0], sigma = [0.0, 0.0] }}]
+            "#,
+            ISSUES
+        ))
+        .unwrap();
+        assert!(consideration.validate().is_ok());
+        let mut sim = Sim::new(2, 400);
+        let mut axes = vec![consideration.new_sim(&sim)];
+        sim.election(&mut axes, &mut trial_rng(2, 0));
+        assert_eq!(sim.nfactions, 2);
+        let mut posns = Vec::new();
+        axes[0].push_posn_elements(&mut |x, _| posns.push(x), &vec![0, 1]);
+        assert_eq!(posns, vec![5.0, 5.0, 5.0, 5.0]);
+        let dist = [
+            (7.0f64 * 7.0 + 5.0 * 5.0).sqrt(),
+            (3.0f64 * 3.0 + 4.0 * 4.0).sqrt(),
+        ];
+        let mut counts = [0, 0];
+        for icit in 0..sim.ncit {
+            let faction = sim.faction[icit];
+            counts[faction] += 1;
+            assert_eq!(sim.scores[(icit, 0)], -dist[faction]);
+        }
+        assert!(counts[0] > 2 * counts[1]);
+
+        let bad = |clusters: &str| {
+            let c: Consideration = toml::from_str(&format!(
+                "[Issues]\nissues = {}\nvoter_clusters = [{}]",
+                ISSUES, clusters
+            ))
+            .unwrap();
+            c.validate().is_err()
+        };
+        assert!(bad("{ center = [0.0], sigma = [1.0, 1.0] }"));
+        assert!(bad("{ center = [0.0, 0.0] }"));
+        assert!(bad(
+            "{ center = [0.0, 0.0], covariance = [[1.0, 2.0], [2.0, 1.0]] }"
+        ));
+        assert!(!bad(
+            "{ center = [0.0, 0.0], covariance = [[1.0, 0.5], [0.5, 1.0]] }"
+        ));
+    }
+
+    #[test]
+    fn test_correlated_issues() {
+        let issues = |options: &str| -> Issues {
+            let c: Consideration = toml::from_str(&format!(
+                "[Issues]\nissues = [{{ sigma = 1.0, halfcsep = 0.0 }}, {{ sigma = 2.0, halfcsep = 0.0 }}]\n{}",
+                options
+            ))
+            .unwrap();
+            match c {
+                Consideration::Issues(issues) => issues,
+                _ => panic!("Expected Issues"),
+            }
+        };
+        let perfect = issues("correlation = [[1.0, 1.0], [1.0, 1.0]]");
+        assert!(perfect.validate().is_ok());
+        assert_eq!(perfect.effective_dim(), 1.0);
+        assert_eq!(issues("").effective_dim(), 25.0 / 17.0);
+        assert_eq!(
+            issues("covariance = [[1.0, 0.0], [0.0, 1.0]]").effective_dim(),
+            2.0
+        );
+        assert!(issues("correlation = [[1.0, 1.5], [1.5, 1.0]]")
+            .validate()
+            .is_err());
+        assert!(issues("correlation = [[2.0, 0.0], [0.0, 1.0]]")
+            .validate()
+            .is_err());
+
+        // Issues inside a Group count too.
+        let group = Consideration::Group(crate::considerations::Group {
+            combine: Default::default(),
+            considerations: vec![Consideration::Issues(perfect.clone())],
+        });
+        assert_eq!(group.issues_eff_dim(), Some(1.0));
+
+        // Perfectly correlated positions lie on a line.
+        let sim = Sim::new(5, 1);
+        let mut issues_sim = perfect.new_sim(&sim);
+        let mut scores = Array2::zeros((1, 5));
+        issues_sim.add_to_scores(&mut scores, &mut trial_rng(3, 0));
+        for row in issues_sim.cand_position.outer_iter() {
+            assert!((2.0 * row[0] - row[1]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_salience() {
+        let spread = |alpha: f64| {
+            let c: Consideration = toml::from_str(&format!(
+                "[Issues]\nissues = {}\nsalience = {{ Dirichlet = {{ alpha = [{1}, {1}] }} }}",
+                ISSUES, alpha
+            ))
+            .unwrap();
+            assert!(c.validate().is_ok());
+            let sim = Sim::new(3, 200);
+            let mut axis = c.new_sim(&sim);
+            let mut scores = Array2::zeros((200, 3));
+            axis.add_to_scores(&mut scores, &mut trial_rng(4, 0));
+            axis.salience_spread().unwrap()
+        };
+        // Nearly equal weights, versus voters who care about one issue
+        assert!(spread(1.0e6) < 0.01);
+        assert!(spread(0.01) > 0.9);
+        // Alphas so small that Gamma draws underflow still give single-issue voters.
+        let tiny = spread(0.003);
+        assert!(tiny > 0.95 && tiny <= 1.0, "{}", tiny);
+
+        let c: Consideration = toml::from_str(&format!(
+            "[Issues]\nissues = {}\nsalience = {{ Dirichlet = {{ alpha = [1.0] }} }}",
+            ISSUES
+        ))
+        .unwrap();
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn test_nomination() {
+        let nominees = |nomination: &str| {
+            let c: Consideration = toml::from_str(&format!(
+                "[Issues]\nissues = [{{ sigma = 1.0, halfcsep = 5.0, halfvsep = 0.0 }}]\nnomination = {}",
+                nomination
+            ))
+            .unwrap();
+            assert!(c.validate().is_ok());
+            let Consideration::Issues(issues) = c else {
+                panic!("Expected Issues");
+            };
+            let sim = Sim::new(5, 100);
+            let mut issues_sim = issues.new_sim(&sim);
+            let mut scores = Array2::zeros((100, 5));
+            issues_sim.add_to_scores(&mut scores, &mut trial_rng(5, 0));
+            let mut voters: Vec<f64> = issues_sim.voter_position.column(0).to_vec();
+            voters.sort_by(f64::total_cmp);
+            let cands = issues_sim.cand_position.column(0).to_vec();
+            (voters, cands)
+        };
+
+        // Candidates sit on voters, ignoring halfcsep.
+        let (voters, cands) = nominees("\"Voters\"");
+        for x in cands.iter() {
+            assert!(voters.contains(x));
+            assert!(x.abs() < 5.0);
+        }
+        let (voters, cands) = nominees("{ Extremes = { quantile = 0.9 } }");
+        let centroid = voters.iter().sum::<f64>() / 100.0;
+        let mut dist: Vec<f64> = voters.iter().map(|x| (x - centroid).abs()).collect();
+        dist.sort_by(f64::total_cmp);
+        for x in cands.iter() {
+            assert!((x - centroid).abs() >= dist[90]);
+        }
+        let (voters, cands) = nominees("{ Activists = { intensity = 2.0 } }");
+        assert!(cands.iter().all(|x| voters.contains(x)));
+
+        let c: Consideration = toml::from_str(
+            "[Issues]\nissues = [{ sigma = 1.0, halfcsep = 0.0 }]\nnomination = { Extremes = { quantile = 1.0 } }",
+        )
+        .unwrap();
+        assert!(c.validate().is_err());
+    }
+
+    #[test]
+    fn test_valence() {
+        let space = |valence: &str| {
+            format!(
+                "Issues = {{ issues = {}, valence = {{ {} }} }}",
+                ISSUES, valence
+            )
+        };
+        // The voter is the centroid, 5 from the candidate.
+        assert_eq!(utility(&space("slope = -1.0")), -10.0);
+        assert_eq!(utility(&space("base = 1.0, slope = 0.2")), -3.0);
+        let c: Consideration = toml::from_str(&space("slope = 0.0, noise = -1.0")).unwrap();
+        assert!(c.validate().is_err());
+    }
+}
@@ -1,9 +1,8 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sim::Sim;
+use crate::sim::{Sim, SimRng};
 use ndarray::Array2;
-use rand::rngs::ThreadRng;
 use rand::Rng;
 use rand_distr::StandardNormal;
 
@@ -49,7 +48,7 @@ impl Likability {
 
 impl ConsiderationSim for LikabilitySim {
     #[allow(unused_variables)]
-    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut ThreadRng) {
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng) {
         let (ncit, ncand) = scores.dim();
 
         self.scores.clear();
@@ -1,40 +1,129 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sim::Sim;
+use crate::sim::{Sim, SimRng};
 use ndarray::Array2;
-use rand::rngs::ThreadRng;
 use std::fmt;
 
+mod empirical;
+mod group;
 mod irrational;
-mod issues;
+pub(crate) mod issues;
 mod likability;
+mod virtues;
 
+pub use empirical::Empirical;
+pub use group::{Combine, CombineScratch, Group};
 pub use irrational::Irrational;
-pub use issues::{new_issues_sim, Issue};
+pub use issues::Issues;
 pub use likability::Likability;
+pub use virtues::Virtues;
 
 pub trait ConsiderationSim: fmt::Debug {
-    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut ThreadRng);
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng);
     fn get_dim(&self) -> usize;
     fn get_name(&self) -> String;
     fn push_posn_elements(&self, report: &mut dyn FnMut(f64, bool), final_candidates: &Vec<usize>);
+    /// The number of factions this consideration divides voters into.
+    /// Voters are only assigned factions by the first consideration with more than one.
+    fn num_factions(&self) -> usize {
+        1
+    }
+    /// The faction of voter icit in the last call to add_to_scores.
+    fn voter_faction(&self, _icit: usize) -> usize {
+        0
+    }
+    /// How unevenly voters weighed this consideration's dimensions in the last
+    /// call to add_to_scores, if they can: the standard deviation of each voter's
+    /// weights (which average 1), averaged over voters.
+    fn salience_spread(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// The factions of the first consideration that divides voters, as in
+/// Sim::get_scores.
+pub fn first_factions(considerations: &[Consideration]) -> usize {
+    considerations
+        .iter()
+        .map(|c| c.num_factions())
+        .find(|&n| n > 1)
+        .unwrap_or(1)
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum Consideration {
     Likability(Likability),
-    Issues(Vec<Issue>),
+    Issues(Issues),
     Irrational(Irrational),
+    Virtues(Virtues),
+    Group(Group),
+    Empirical(Empirical),
 }
 
 impl Consideration {
     pub fn new_sim(&self, sim: &Sim) -> Box<dyn ConsiderationSim> {
         match self {
             Consideration::Likability(c) => Box::new(c.new_sim(sim)),
-            Consideration::Issues(issues) => Box::new(new_issues_sim(issues.clone(), sim)),
+            Consideration::Issues(issues) => Box::new(issues.new_sim(sim)),
             Consideration::Irrational(c) => Box::new(c.new_sim(sim)),
+            Consideration::Virtues(c) => Box::new(c.new_sim(sim)),
+            Consideration::Group(c) => Box::new(c.new_sim(sim)),
+            Consideration::Empirical(c) => Box::new(c.new_sim(sim)),
+        }
+    }
+
+    /// The effective number of dimensions of the first Issues, looking inside
+    /// Groups. It comes from the config alone, so it's the same for every trial.
+    pub fn issues_eff_dim(&self) -> Option<f64> {
+        match self {
+            Consideration::Issues(issues) => Some(issues.effective_dim()),
+            Consideration::Group(g) => g.considerations.iter().find_map(|c| c.issues_eff_dim()),
+            _ => None,
+        }
+    }
+
+    /// The number of voter factions its sim will report.
+    pub fn num_factions(&self) -> usize {
+        match self {
+            Consideration::Issues(issues) => issues.num_factions(),
+            Consideration::Irrational(c) => c.camps.max(1),
+            Consideration::Virtues(c) => c.clusters.max(1),
+            Consideration::Group(g) => first_factions(&g.considerations),
+            Consideration::Likability(_) | Consideration::Empirical(_) => 1,
+        }
+    }
+
+    /// Whether every utility this consideration gives is zero or more.
+    pub fn never_negative(&self) -> bool {
+        match self {
+            Consideration::Likability(c) => c.mean >= 0.0,
+            Consideration::Issues(Issues::List(_)) => false,
+            Consideration::Issues(Issues::Space(space)) => {
+                space.utility_fn.never_negative() && space.valence.is_none()
+            }
+            Consideration::Irrational(c) => c.sigma >= 0.0,
+            Consideration::Virtues(c) => c.never_negative(),
+            Consideration::Group(g) => g.considerations.iter().all(|c| c.never_negative()),
+            Consideration::Empirical(c) => c.utility_fn.never_negative(),
+        }
+    }
+
+    /// Checks settings that deserialize fine but can't be simulated.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Consideration::Virtues(v) => v.validate(),
+            Consideration::Group(g) => {
+                if g.considerations.is_empty() {
+                    return Err("A Group needs at least one consideration".to_string());
+                }
+                g.combine.validate(&g.considerations)?;
+                g.considerations.iter().try_for_each(|c| c.validate())
+            }
+            Consideration::Issues(issues) => issues.validate(),
+            Consideration::Empirical(c) => c.validate(),
+            _ => Ok(()),
         }
     }
 }
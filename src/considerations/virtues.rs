@@ -0,0 +1,279 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sim::{Sim, SimRng};
+use ndarray::Array2;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+use super::ConsiderationSim;
+
+/// Virtues are qualities, such as honesty, competence, or compassion, that each
+/// candidate expresses to some degree. Each voter is sensitive to each virtue
+/// by some factor, which may be negative: a voter may dislike a virtue that
+/// others admire. A voter's utility for a candidate is the sum over virtues of
+/// sensitivity times expression.
+///
+/// Voters can be clustered, when clusters > 1. (icit % clusters) gives the cluster
+/// index. Each cluster shares a sensitivity vector, drawn from `sensitivity`, and
+/// each voter deviates from their cluster's by a normal variate with standard
+/// deviation `cluster_spread`. The cluster is the voter's faction.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Virtues {
+    /// The number of virtues
+    pub virtues: usize,
+    /// How strongly candidates express each virtue
+    #[serde(default = "default_expression")]
+    pub expression: VirtueDist,
+    /// How much voters care about each virtue
+    #[serde(default = "default_sensitivity")]
+    pub sensitivity: VirtueDist,
+    #[serde(default = "default_clusters")]
+    pub clusters: usize,
+    #[serde(default)]
+    pub cluster_spread: f64,
+}
+
+/// Distributions of virtue expressions and sensitivities
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum VirtueDist {
+    Normal {
+        mean: f64,
+        sigma: f64,
+    },
+    Uniform {
+        low: f64,
+        high: f64,
+    },
+    /// Positive, with a Chi-square distribution of one degree of freedom like Likability
+    ChiSquare {
+        mean: f64,
+    },
+}
+
+fn default_expression() -> VirtueDist {
+    VirtueDist::ChiSquare { mean: 0.5 }
+}
+
+fn default_sensitivity() -> VirtueDist {
+    VirtueDist::Normal {
+        mean: 0.5,
+        sigma: 0.5,
+    }
+}
+
+fn default_clusters() -> usize {
+    1
+}
+
+impl VirtueDist {
+    fn validate(&self) -> Result<(), String> {
+        let ok = match *self {
+            VirtueDist::Normal { mean, sigma } => mean.is_finite() && sigma >= 0.0,
+            VirtueDist::Uniform { low, high } => low.is_finite() && high.is_finite() && low <= high,
+            VirtueDist::ChiSquare { mean } => mean >= 0.0,
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("Invalid virtue distribution {:?}", self))
+        }
+    }
+
+    fn never_negative(&self) -> bool {
+        match *self {
+            VirtueDist::Normal { mean, sigma } => sigma == 0.0 && mean >= 0.0,
+            VirtueDist::Uniform { low, .. } => low >= 0.0,
+            VirtueDist::ChiSquare { .. } => true,
+        }
+    }
+
+    fn sample(&self, rng: &mut SimRng) -> f64 {
+        match *self {
+            VirtueDist::Normal { mean, sigma } => {
+                let x: f64 = rng.sample(StandardNormal);
+                mean + x * sigma
+            }
+            VirtueDist::Uniform { low, high } => rng.random_range(low..=high),
+            VirtueDist::ChiSquare { mean } => {
+                let x: f64 = rng.sample(StandardNormal);
+                x.powi(2) * mean
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VirtuesSim {
+    p: Virtues,                       // Parameters
+    expression: Array2<f64>,          // (cand, virtue)
+    cluster_sensitivity: Array2<f64>, // (cluster, virtue)
+    sensitivity: Vec<f64>,            // One voter's, reused
+}
+
+impl Virtues {
+    pub fn new_sim(&self, sim: &Sim) -> VirtuesSim {
+        VirtuesSim {
+            p: self.clone(),
+            expression: Array2::zeros((sim.ncand, self.virtues)),
+            cluster_sensitivity: Array2::zeros((self.clusters.max(1), self.virtues)),
+            sensitivity: vec![0.0; self.virtues],
+        }
+    }
+
+    /// Checks that there are virtues and voter clusters, and that the
+    /// distributions can be sampled.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.virtues == 0 {
+            return Err("Virtues needs at least one virtue".to_string());
+        }
+        if self.clusters == 0 {
+            return Err("Virtues needs at least one voter cluster".to_string());
+        }
+        if self.cluster_spread.is_nan() || self.cluster_spread < 0.0 {
+            return Err(format!(
+                "Virtues cluster_spread must not be negative, got {}",
+                self.cluster_spread
+            ));
+        }
+        self.expression.validate()?;
+        self.sensitivity.validate()
+    }
+
+    /// Whether expressions and sensitivities are all zero or more, so that
+    /// utilities are too.
+    pub fn never_negative(&self) -> bool {
+        let spreads = self.uses_clusters() && self.cluster_spread > 0.0;
+        self.expression.never_negative() && self.sensitivity.never_negative() && !spreads
+    }
+
+    fn uses_clusters(&self) -> bool {
+        self.clusters > 1
+    }
+}
+
+impl ConsiderationSim for VirtuesSim {
+    fn add_to_scores(&mut self, scores: &mut Array2<f64>, rng: &mut SimRng) {
+        let (ncit, ncand) = scores.dim();
+        for x in self.expression.iter_mut() {
+            *x = self.p.expression.sample(rng);
+        }
+        if self.p.uses_clusters() {
+            for s in self.cluster_sensitivity.iter_mut() {
+                *s = self.p.sensitivity.sample(rng);
+            }
+        }
+        for icit in 0..ncit {
+            for (ivirtue, s) in self.sensitivity.iter_mut().enumerate() {
+                *s = if self.p.uses_clusters() {
+                    let deviation: f64 = rng.sample(StandardNormal);
+                    self.cluster_sensitivity[(icit % self.p.clusters, ivirtue)]
+                        + deviation * self.p.cluster_spread
+                } else {
+                    self.p.sensitivity.sample(rng)
+                };
+            }
+            for icand in 0..ncand {
+                let utility: f64 = self
+                    .sensitivity
+                    .iter()
+                    .zip(self.expression.row(icand).iter())
+                    .map(|(s, x)| s * x)
+                    .sum();
+                scores[(icit, icand)] += utility;
+            }
+        }
+    }
+
+    fn get_dim(&self) -> usize {
+        self.p.virtues
+    }
+
+    fn get_name(&self) -> String {
+        "virtues".to_string()
+    }
+
+    fn push_posn_elements(&self, report: &mut dyn FnMut(f64, bool), final_candidates: &Vec<usize>) {
+        let nvirtues = self.p.virtues;
+        for &fc in final_candidates.iter() {
+            for ivirtue in 0..nvirtues {
+                report(self.expression[(fc, ivirtue)], ivirtue == nvirtues - 1);
+            }
+        }
+    }
+
+    fn num_factions(&self) -> usize {
+        if self.p.uses_clusters() {
+            self.p.clusters
+        } else {
+            1
+        }
+    }
+
+    fn voter_faction(&self, icit: usize) -> usize {
+        icit % self.num_factions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::trial_rng;
+
+    #[test]
+    fn test_clustered_virtues() {
+        let virtues = Virtues {
+            virtues: 3,
+            expression: default_expression(),
+            sensitivity: VirtueDist::Uniform {
+                low: -1.0,
+                high: 1.0,
+            },
+            clusters: 2,
+            cluster_spread: 0.0,
+        };
+        let sim = Sim::new(4, 10);
+        let mut vsim = virtues.new_sim(&sim);
+        let mut scores = Array2::zeros((10, 4));
+        vsim.add_to_scores(&mut scores, &mut trial_rng(1, 0));
+
+        // Without spread, voters in the same cluster agree exactly.
+        for icit in 2..10 {
+            assert_eq!(scores.row(icit), scores.row(icit % 2));
+        }
+        assert_ne!(scores.row(0), scores.row(1));
+        assert_eq!(vsim.num_factions(), 2);
+        assert_eq!(vsim.voter_faction(5), 1);
+
+        let mut posns = Vec::new();
+        vsim.push_posn_elements(&mut |x, _| posns.push(x), &vec![2]);
+        assert_eq!(posns, vsim.expression.row(2).to_vec());
+        assert!(posns.iter().all(|&x| x >= 0.0));
+        assert!(virtues.validate().is_ok());
+
+        for sensitivity in [
+            VirtueDist::Uniform {
+                low: 1.0,
+                high: -1.0,
+            },
+            VirtueDist::Uniform {
+                low: f64::NAN,
+                high: 1.0,
+            },
+            VirtueDist::ChiSquare { mean: -0.5 },
+        ] {
+            let bad = Virtues {
+                sensitivity,
+                ..virtues.clone()
+            };
+            assert!(bad.validate().is_err());
+        }
+        let bad = Virtues {
+            clusters: 0,
+            ..virtues.clone()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -48,12 +48,71 @@ impl CovMatrix {
     }
 }
 
+/// Returns the lower-triangular L with L * L^T = cov, or an error if cov isn't a
+/// symmetric positive semi-definite matrix. Semi-definite matrices get zero
+/// columns where they have no variance left.
+pub fn cholesky(cov: &Array2<f64>) -> Result<Array2<f64>, String> {
+    let (n, ncols) = cov.dim();
+    if n != ncols {
+        return Err(format!("Covariance matrix must be square, got {} x {}", n, ncols));
+    }
+    let scale = cov.diag().iter().fold(0.0_f64, |m, &x| m.max(x.abs()));
+    let tolerance = 1.0e-10 * scale.max(f64::MIN_POSITIVE);
+    let mut l = Array2::zeros((n, n));
+    for j in 0..n {
+        for i in 0..j {
+            if (cov[(i, j)] - cov[(j, i)]).abs() > tolerance {
+                return Err(format!("Covariance matrix is not symmetric: {}", cov));
+            }
+        }
+        let pivot = cov[(j, j)] - (0..j).map(|k| l[(j, k)] * l[(j, k)]).sum::<f64>();
+        if pivot < -tolerance {
+            return Err(format!(
+                "Covariance matrix is not positive semi-definite: {}",
+                cov
+            ));
+        }
+        if pivot <= tolerance {
+            // No variance left along this axis. Anything correlated with it
+            // must be fully determined by the previous axes too.
+            for i in (j + 1)..n {
+                let rest = cov[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>();
+                if rest.abs() > tolerance.sqrt() {
+                    return Err(format!(
+                        "Covariance matrix is not positive semi-definite: {}",
+                        cov
+                    ));
+                }
+            }
+            continue;
+        }
+        let d = pivot.sqrt();
+        l[(j, j)] = d;
+        for i in (j + 1)..n {
+            l[(i, j)] = (cov[(i, j)] - (0..j).map(|k| l[(i, k)] * l[(j, k)]).sum::<f64>()) / d;
+        }
+    }
+    Ok(l)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use float_eq::assert_float_eq;
     use ndarray::array;
 
+    #[test]
+    fn test_cholesky() {
+        let cov = array![[4.0, 2.0, 0.0], [2.0, 2.0, 0.0], [0.0, 0.0, 0.0]];
+        let l = cholesky(&cov).unwrap();
+        assert_eq!(l, array![[2.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 0.0]]);
+        // Perfectly correlated axes are fine,
+        assert!(cholesky(&array![[1.0, 1.0], [1.0, 1.0]]).is_ok());
+        // but more than perfectly correlated is impossible.
+        assert!(cholesky(&array![[1.0, 2.0], [2.0, 1.0]]).is_err());
+        assert!(cholesky(&array![[1.0, 0.5], [0.0, 1.0]]).is_err());
+    }
+
     #[test]
     fn test_cov_matrix() {
         // Using Python's numpy.cov for comparison.
@@ -0,0 +1,136 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+
+use crate::config::Config;
+use crate::run::Election;
+
+/// Regenerates trial `trial` of a run with the given seed, and prints everything
+/// that went into it. The config must match the run's, including any command-line
+/// overrides.
+pub fn explain(config: &Config, seed: u64, trial: usize) -> Result<(), Box<dyn Error>> {
+    if config.sweep.is_some() {
+        return Err(
+            "explain replays a single config: replace the sweep with the point's values".into(),
+        );
+    }
+    let mut election = Election::new(config, 1);
+    election.run_trial(seed, trial);
+    let sim = &election.sim;
+
+    println!("Trial {} of seed {}", trial, seed);
+    if let Some(eff_dim) = config
+        .considerations
+        .iter()
+        .find_map(|c| c.issues_eff_dim())
+    {
+        println!(
+            "Issues' effective number of dimensions, the same for every trial: {:.3}",
+            eff_dim
+        );
+    }
+
+    if let Some(sim_primary) = &election.sim_primary {
+        println!();
+        println!(
+            "Primary election of {} candidates. Winners, numbered below as 0..{}: {:?}",
+            sim_primary.ncand,
+            sim.ncand - 1,
+            election.final_cands
+        );
+    }
+
+    println!();
+    println!("Candidates:");
+    for icand in 0..sim.ncand {
+        println!(
+            "  {}: regret {:.4}{}",
+            icand,
+            sim.regrets[icand],
+            if sim.in_smith_set[icand] {
+                ", in Smith set"
+            } else {
+                ""
+            }
+        );
+    }
+
+    println!();
+    println!("Candidate positions:");
+    for axis in election.axes.iter() {
+        println!("  {}:", axis.get_name());
+        let mut row = Vec::new();
+        let mut icand = 0;
+        axis.push_posn_elements(
+            &mut |x, next_row| {
+                row.push(format!("{:.4}", x));
+                if next_row {
+                    println!("    {}: {}", icand, row.join(", "));
+                    row.clear();
+                    icand += 1;
+                }
+            },
+            &election.final_cands,
+        );
+    }
+
+    println!();
+    println!("Voter utilities (faction, strategic draw: utilities by candidate):");
+    for (icit, utilities) in sim.utilities.outer_iter().enumerate() {
+        let utilities: Vec<String> = utilities.iter().map(|u| format!("{:8.4}", u)).collect();
+        println!(
+            "  {:4} ({}, {:.3}): {}",
+            icit,
+            sim.faction[icit],
+            sim.strat_draw[icit],
+            utilities.join(" ")
+        );
+    }
+
+    if sim.perception.is_some() {
+        println!();
+        println!("Perceived utilities, which ballots are cast on:");
+        for (icit, scores) in sim.scores.outer_iter().enumerate() {
+            let scores: Vec<String> = scores.iter().map(|u| format!("{:8.4}", u)).collect();
+            println!("  {:4}: {}", icit, scores.join(" "));
+        }
+    }
+
+    println!();
+    println!("Pairwise margins (row beats column by):");
+    for row in sim.i_beats_j_by.outer_iter() {
+        let margins: Vec<String> = row.iter().map(|m| format!("{:5}", m)).collect();
+        println!("  {}", margins.join(" "));
+    }
+    let smith_set: Vec<usize> = (0..sim.ncand).filter(|&c| sim.in_smith_set[c]).collect();
+    println!("Smith set: {:?}", smith_set);
+
+    println!();
+    println!("Methods:");
+    for (method, result) in election.methods.iter().zip(election.results.iter()) {
+        let result = result.unwrap();
+        println!(
+            "  {}{}",
+            method.method.name(),
+            if method.is_visible() {
+                ""
+            } else {
+                " (inserted poll)"
+            }
+        );
+        for line in method.method.explain(sim) {
+            println!("    {}", line);
+        }
+        println!(
+            "    winner {} ({}), runner-up {} ({}), regret {:.4}",
+            result.winner.cand,
+            result.winner.score,
+            result.runnerup.cand,
+            result.runnerup.score,
+            sim.regrets[result.winner.cand]
+        );
+    }
+
+    Ok(())
+}
@@ -3,18 +3,24 @@
 
 use std::error::Error;
 use std::ffi::OsString;
+use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::process;
 
 // Local libraries
+mod committee;
 mod config;
 mod considerations;
 mod cov_matrix;
+mod explain;
 mod method_tracker;
 mod methods;
+mod perception;
 mod run;
 mod sim;
+mod sweep;
+mod yee;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -42,6 +48,31 @@ struct Args {
     /// number of candidates in a primary (RRV) election. (No primary by default.)
     #[arg(short, long)]
     primary_candidates: Option<usize>,
+
+    /// Random number seed (override config)
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Regenerate one election from a run and print how it was decided.
+    /// Use the same config and overrides as the run.
+    Explain {
+        /// The run's seed
+        seed: u64,
+        /// The trial index within the run
+        trial: usize,
+    },
+    /// Draw Yee diagrams: candidates stay fixed in a 2-D issue space while
+    /// the voters' center sweeps a grid. Needs a [yee] section in the config.
+    Yee {
+        /// Directory for yee.csv and one SVG image per method
+        outdir: PathBuf,
+    },
 }
 
 fn main() {
@@ -64,8 +95,15 @@ fn run() -> Result<(), Box<dyn Error>> {
     if let Some(pcand) = args.primary_candidates {
         config.primary_candidates = Some(pcand);
     }
+    if let Some(seed) = args.seed {
+        config.seed = Some(seed);
+    }
 
     pretty_env_logger::init();
 
-    run::run(&config, args.trials, &args.outfile)
+    match args.command {
+        Some(Command::Explain { seed, trial }) => explain::explain(&config, seed, trial),
+        Some(Command::Yee { outdir }) => yee::yee(&config, &outdir),
+        None => run::run(&config, args.trials, &args.outfile),
+    }
 }
@@ -3,35 +3,97 @@
 
 use std::sync::Arc;
 
-use arrow_array::builder::PrimitiveBuilder;
+use arrow_array::builder::{
+    BooleanBuilder, Float64Builder, Int32Builder, ListBuilder, PrimitiveBuilder,
+};
 use arrow_array::types::{Float64Type, Int32Type};
 use arrow_array::{ArrayRef, Float64Array, Int32Array, StructArray};
-use arrow_schema::{DataType, Field, Fields};
+use arrow_schema::{DataType, Field, FieldRef, Fields};
 use meansd::MeanSD;
+use ndarray::Array2;
 
-use crate::methods::{Method, MethodSim, WinnerAndRunnerup};
+use crate::committee::CommitteeMetrics;
+use crate::methods::{
+    fill_range_ballots, MWMethodSim, Method, MethodSim, MultiWinMethod, Strategy, WinnerAndRunnerup,
+};
 use crate::sim::Sim;
 
 pub struct MethodTracker {
     pub method: Box<dyn MethodSim>,
+    visible: bool,         // False for honest polls inserted as strategic prerequisites
+    prereq: Option<usize>, // Index of the tracker running this method's honest poll
     ntrials: usize,
     ntrials_subopt: usize,
+    ntrials_proven: usize,
     mean_regret: MeanSD,
     mean_subopt_regret: MeanSD,
     result_bldr: PrimitiveBuilder<Float64Type>,
     winner_bldr: PrimitiveBuilder<Int32Type>,
+    proven_bldr: Option<BooleanBuilder>, // Only for methods reporting proven_optimal
+    strategy: Option<StrategyTracker>,   // Only for strategic methods
+}
+
+/// Tracks how much strategic voting pays off, by comparing each voter's utility
+/// for the winner against the winner of the honest pre-election poll.
+struct StrategyTracker {
+    mean_gain: MeanSD,
+    frac_bldr: PrimitiveBuilder<Float64Type>,
+    gain_bldr: PrimitiveBuilder<Float64Type>,
+    faction_gain_bldr: ListBuilder<Float64Builder>,
+    faction_gain: Vec<f64>,
+    faction_size: Vec<usize>,
+}
+
+/// Creates a tracker for each method. A strategic method gets its honest poll
+/// from the first tracker already running that poll. Failing that, a hidden
+/// tracker for the poll is inserted just ahead of it.
+pub fn new_method_trackers(methods: &[Method], sim: &Sim, max_trials: usize) -> Vec<MethodTracker> {
+    let mut trackers: Vec<MethodTracker> = Vec::with_capacity(methods.len());
+    for method in methods.iter() {
+        let mut tracker = MethodTracker::new(method, sim, max_trials);
+        if let Some(poll) = tracker.method.strategic_prereq() {
+            let mut poll = MethodTracker::new(&poll, sim, max_trials);
+            let poll_colname = poll.colname();
+            tracker.prereq = trackers.iter().position(|t| t.colname() == poll_colname);
+            if tracker.prereq.is_none() {
+                log::debug!(
+                    "Inserting {} as a poll for {}",
+                    poll_colname,
+                    tracker.colname()
+                );
+                poll.visible = false;
+                tracker.prereq = Some(trackers.len());
+                trackers.push(poll);
+            }
+        }
+        trackers.push(tracker);
+    }
+    trackers
 }
 
 impl MethodTracker {
     pub fn new(method: &Method, sim: &Sim, max_trials: usize) -> MethodTracker {
+        let method = method.new_sim(sim);
+        let proven_bldr = method
+            .proven_optimal()
+            .map(|_| BooleanBuilder::with_capacity(max_trials));
+        let strategy = match method.strat() {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => Some(StrategyTracker::new(max_trials)),
+        };
         MethodTracker {
-            method: method.new_sim(sim),
+            method,
+            visible: true,
+            prereq: None,
             ntrials: 0,
             ntrials_subopt: 0,
+            ntrials_proven: 0,
             mean_regret: MeanSD::default(),
             mean_subopt_regret: MeanSD::default(),
             result_bldr: Float64Array::builder(max_trials),
             winner_bldr: Int32Array::builder(max_trials),
+            proven_bldr,
+            strategy,
         }
     }
 
@@ -57,6 +119,16 @@ impl MethodTracker {
         self.result_bldr.append_value(regret);
         self.winner_bldr
             .append_value(sim.regret_rank[result.winner.cand] as i32);
+        if let Some(proven_bldr) = self.proven_bldr.as_mut() {
+            let proven = self.method.proven_optimal().unwrap();
+            if proven {
+                self.ntrials_proven += 1;
+            }
+            proven_bldr.append_value(proven);
+        }
+        if let Some(strategy) = self.strategy.as_mut() {
+            strategy.update(sim, self.method.strat(), &result, honest_rslt);
+        }
         result
     }
 
@@ -64,11 +136,29 @@ impl MethodTracker {
         self.method.colname()
     }
 
-    pub fn data_type() -> DataType {
-        DataType::Struct(Fields::from(vec![
+    /// Honest polls that were only added as a strategic method's prerequisite
+    /// are not written out or summarized.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The index of the tracker whose result this method uses as its honest poll.
+    pub fn prereq(&self) -> Option<usize> {
+        self.prereq
+    }
+
+    pub fn data_type(&self) -> DataType {
+        let mut fields = vec![
             Arc::new(Field::new("winner", DataType::Int32, false)),
             Arc::new(Field::new("regret", DataType::Float64, false)),
-        ]))
+        ];
+        if self.proven_bldr.is_some() {
+            fields.push(Arc::new(Field::new("proven", DataType::Boolean, false)));
+        }
+        if self.strategy.is_some() {
+            fields.extend(StrategyTracker::fields());
+        }
+        DataType::Struct(Fields::from(fields))
     }
 
     // pub fn get_field(&self) -> Field {
@@ -77,7 +167,7 @@ impl MethodTracker {
 
     pub fn get_column(&mut self) -> arrow_array::ArrayRef {
         // Arc::new(self.result_bldr.finish())
-        let struct_array = StructArray::from(vec![
+        let mut columns = vec![
             (
                 Arc::new(Field::new("winner", DataType::Int32, false)),
                 Arc::new(self.winner_bldr.finish()) as ArrayRef,
@@ -86,7 +176,17 @@ impl MethodTracker {
                 Arc::new(Field::new("regret", DataType::Float64, false)),
                 Arc::new(self.result_bldr.finish()) as ArrayRef,
             ),
-        ]);
+        ];
+        if let Some(proven_bldr) = self.proven_bldr.as_mut() {
+            columns.push((
+                Arc::new(Field::new("proven", DataType::Boolean, false)),
+                Arc::new(proven_bldr.finish()) as ArrayRef,
+            ));
+        }
+        if let Some(strategy) = self.strategy.as_mut() {
+            columns.extend(StrategyTracker::fields().into_iter().zip(strategy.finish()));
+        }
+        let struct_array = StructArray::from(columns);
         Arc::new(struct_array)
     }
 
@@ -108,9 +208,99 @@ impl MethodTracker {
             name: self.method.name(),
             ntrials: self.ntrials,
             ntrials_subopt: self.ntrials_subopt,
+            ntrials_proven: self.proven_bldr.as_ref().map(|_| self.ntrials_proven),
             mean_regret: self.mean_regret.clone(),
             mean_subopt_regret: self.mean_subopt_regret.clone(),
+            mean_strat_gain: self.strategy.as_ref().map(|s| s.mean_gain),
+        }
+    }
+}
+
+impl StrategyTracker {
+    fn new(max_trials: usize) -> StrategyTracker {
+        StrategyTracker {
+            mean_gain: MeanSD::default(),
+            frac_bldr: Float64Array::builder(max_trials),
+            gain_bldr: Float64Array::builder(max_trials),
+            faction_gain_bldr: ListBuilder::new(Float64Builder::new()),
+            faction_gain: Vec::new(),
+            faction_size: Vec::new(),
+        }
+    }
+
+    /// strat_frac is the fraction of voters who voted strategically.
+    /// strat_gain is the mean utility gained by voters who voted strategically.
+    /// faction_gain lists the mean utility gained by each faction as a whole.
+    /// Gains are null without an honest poll, or when nobody voted strategically.
+    fn fields() -> Vec<FieldRef> {
+        vec![
+            Arc::new(Field::new("strat_frac", DataType::Float64, false)),
+            Arc::new(Field::new("strat_gain", DataType::Float64, true)),
+            Arc::new(Field::new(
+                "faction_gain",
+                DataType::List(Arc::new(Field::new("item", DataType::Float64, true))),
+                true,
+            )),
+        ]
+    }
+
+    fn update(
+        &mut self,
+        sim: &Sim,
+        strat: Strategy,
+        result: &WinnerAndRunnerup,
+        honest_rslt: Option<WinnerAndRunnerup>,
+    ) {
+        let nstrat = (0..sim.ncit)
+            .filter(|&icit| sim.is_strategic(icit, strat))
+            .count();
+        self.frac_bldr.append_value(nstrat as f64 / sim.ncit as f64);
+        let Some(honest_rslt) = honest_rslt else {
+            self.gain_bldr.append_null();
+            self.faction_gain_bldr.append(false);
+            return;
+        };
+
+        self.faction_gain.clear();
+        self.faction_gain.resize(sim.nfactions, 0.0);
+        self.faction_size.clear();
+        self.faction_size.resize(sim.nfactions, 0);
+        let mut strat_gain = 0.0;
+        for icit in 0..sim.ncit {
+            let gain = sim.utilities[(icit, result.winner.cand)]
+                - sim.utilities[(icit, honest_rslt.winner.cand)];
+            self.faction_gain[sim.faction[icit]] += gain;
+            self.faction_size[sim.faction[icit]] += 1;
+            if sim.is_strategic(icit, strat) {
+                strat_gain += gain;
+            }
         }
+
+        if nstrat > 0 {
+            let strat_gain = strat_gain / nstrat as f64;
+            self.mean_gain.update(strat_gain);
+            self.gain_bldr.append_value(strat_gain);
+        } else {
+            self.gain_bldr.append_null();
+        }
+        for (&gain, &size) in self.faction_gain.iter().zip(self.faction_size.iter()) {
+            if size > 0 {
+                self.faction_gain_bldr
+                    .values()
+                    .append_value(gain / size as f64);
+            } else {
+                self.faction_gain_bldr.values().append_null();
+            }
+        }
+        self.faction_gain_bldr.append(true);
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.frac_bldr.finish()) as ArrayRef,
+            Arc::new(self.gain_bldr.finish()) as ArrayRef,
+            Arc::new(self.faction_gain_bldr.finish()) as ArrayRef,
+        ]
     }
 }
 
@@ -118,8 +308,10 @@ pub struct SendableMethodReport {
     pub name: String,
     pub ntrials: usize,
     pub ntrials_subopt: usize,
+    pub ntrials_proven: Option<usize>,
     pub mean_regret: MeanSD,
     pub mean_subopt_regret: MeanSD,
+    pub mean_strat_gain: Option<MeanSD>,
 }
 
 impl SendableMethodReport {
@@ -127,20 +319,305 @@ impl SendableMethodReport {
         assert!(self.name == other.name);
         self.ntrials += other.ntrials;
         self.ntrials_subopt += other.ntrials_subopt;
+        if let (Some(proven), Some(other_proven)) =
+            (self.ntrials_proven.as_mut(), other.ntrials_proven)
+        {
+            *proven += other_proven;
+        }
         self.mean_regret += other.mean_regret;
         self.mean_subopt_regret += other.mean_subopt_regret;
+        if let (Some(gain), Some(other_gain)) =
+            (self.mean_strat_gain.as_mut(), other.mean_strat_gain)
+        {
+            *gain += other_gain;
+        }
     }
 
     pub fn report(&self) {
         let frac_suboptimal = self.ntrials_subopt as f64 / self.ntrials as f64;
+        let frac_proven = match self.ntrials_proven {
+            Some(proven) => format!(
+                ", frac proven optimal: {}",
+                proven as f64 / self.ntrials as f64
+            ),
+            None => String::new(),
+        };
+        let strat_gain = match &self.mean_strat_gain {
+            Some(gain) => format!(", avg strategic voter gain: {}", gain.mean()),
+            None => String::new(),
+        };
         println!(
-            "Method {}: Avg Regret: {}, σ: {}, Frac suboptimal winner: {}, avg subopt regret: {}, {} elections",
+            "Method {}: Avg Regret: {}, σ: {}, Frac suboptimal winner: {}, avg subopt regret: {}, {} elections{}{}",
             self.name,
             self.mean_regret.mean(),
             self.mean_regret.sstdev(),
             frac_suboptimal,
             self.mean_subopt_regret.mean(),
             self.ntrials,
+            frac_proven,
+            strat_gain,
+        );
+    }
+}
+
+/// Tracks the committees a multi-winner method elects from the general
+/// election's candidates, and how well they serve the voters.
+pub struct CommitteeTracker {
+    pub method: Box<dyn MWMethodSim>,
+    colname: String,
+    seats: usize,
+    approvals: Array2<i32>, // (cit, cand), as for Range with 2 ranks
+    ntrials: usize,
+    ntrials_jr: usize,
+    ntrials_ejr: usize,
+    mean_total_utility: MeanSD,
+    mean_rank_utility: MeanSD,
+    mean_coverage: MeanSD,
+    winners_bldr: ListBuilder<Int32Builder>,
+    total_utility_bldr: PrimitiveBuilder<Float64Type>,
+    rank_utility_bldr: PrimitiveBuilder<Float64Type>,
+    coverage_bldr: PrimitiveBuilder<Float64Type>,
+    prop_degree_bldr: ListBuilder<Float64Builder>,
+    jr_bldr: BooleanBuilder,
+    ejr_bldr: BooleanBuilder,
+    iterations_bldr: PrimitiveBuilder<Int32Type>,
+    cycle_detected_bldr: BooleanBuilder,
+}
+
+/// Creates a tracker for each method. Column names are the methods' names,
+/// numbered from the second use of a name on.
+pub fn new_committee_trackers(
+    methods: &[MultiWinMethod],
+    seats: usize,
+    sim: &Sim,
+    max_trials: usize,
+) -> Vec<CommitteeTracker> {
+    let mut trackers: Vec<CommitteeTracker> = Vec::with_capacity(methods.len());
+    for method in methods.iter() {
+        let name = method.name();
+        let nprior = methods
+            .iter()
+            .take(trackers.len())
+            .filter(|m| m.name() == name)
+            .count();
+        let colname = if nprior == 0 {
+            name
+        } else {
+            format!("{}_{}", name, nprior + 1)
+        };
+        trackers.push(CommitteeTracker::new(
+            method, colname, seats, sim, max_trials,
+        ));
+    }
+    trackers
+}
+
+impl CommitteeTracker {
+    pub fn new(
+        method: &MultiWinMethod,
+        colname: String,
+        seats: usize,
+        sim: &Sim,
+        max_trials: usize,
+    ) -> CommitteeTracker {
+        CommitteeTracker {
+            method: method.new_sim(sim),
+            colname,
+            seats: seats.min(sim.ncand),
+            approvals: Array2::zeros((sim.ncit, sim.ncand)),
+            ntrials: 0,
+            ntrials_jr: 0,
+            ntrials_ejr: 0,
+            mean_total_utility: MeanSD::default(),
+            mean_rank_utility: MeanSD::default(),
+            mean_coverage: MeanSD::default(),
+            winners_bldr: ListBuilder::new(Int32Builder::with_capacity(max_trials * seats)),
+            total_utility_bldr: Float64Array::builder(max_trials),
+            rank_utility_bldr: Float64Array::builder(max_trials),
+            coverage_bldr: Float64Array::builder(max_trials),
+            prop_degree_bldr: ListBuilder::new(Float64Builder::with_capacity(max_trials * seats)),
+            jr_bldr: BooleanBuilder::with_capacity(max_trials),
+            ejr_bldr: BooleanBuilder::with_capacity(max_trials),
+            iterations_bldr: Int32Array::builder(max_trials),
+            cycle_detected_bldr: BooleanBuilder::with_capacity(max_trials),
+        }
+    }
+
+    pub fn elect(&mut self, sim: &Sim) {
+        let results = self.method.multi_elect(sim, None, self.seats);
+        let committee: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        fill_range_ballots(sim, 2, &mut self.approvals);
+        let metrics =
+            CommitteeMetrics::compute(&sim.utilities, &self.approvals, &committee, self.seats);
+
+        self.ntrials += 1;
+        if metrics.jr {
+            self.ntrials_jr += 1;
+        }
+        if metrics.ejr {
+            self.ntrials_ejr += 1;
+        }
+        self.mean_total_utility.update(metrics.total_utility);
+        self.mean_rank_utility.update(metrics.rank_utility);
+        self.mean_coverage.update(metrics.coverage);
+
+        for &icand in committee.iter() {
+            self.winners_bldr
+                .values()
+                .append_value(sim.regret_rank[icand] as i32);
+        }
+        self.winners_bldr.append(true);
+        self.total_utility_bldr.append_value(metrics.total_utility);
+        self.rank_utility_bldr.append_value(metrics.rank_utility);
+        self.coverage_bldr.append_value(metrics.coverage);
+        for &pd in metrics.prop_degree.iter() {
+            self.prop_degree_bldr.values().append_option(pd);
+        }
+        self.prop_degree_bldr.append(true);
+        self.jr_bldr.append_value(metrics.jr);
+        self.ejr_bldr.append_value(metrics.ejr);
+        let stats = self.method.iteration_stats();
+        self.iterations_bldr
+            .append_option(stats.map(|(iterations, _)| iterations as i32));
+        self.cycle_detected_bldr
+            .append_option(stats.map(|(_, cycle_detected)| cycle_detected));
+        log::debug!(
+            "Committee {} elected {:?} -- {:?}",
+            self.colname,
+            committee,
+            metrics
+        );
+    }
+
+    pub fn colname(&self) -> String {
+        self.colname.clone()
+    }
+
+    /// winners are the committee's regret ranks, in the order elected.
+    /// prop_degree lists the proportionality degree for l = 1 to seats.
+    /// iterations and cycle_detected are null for methods that don't iterate.
+    fn fields() -> Vec<FieldRef> {
+        let list_of = |data_type| DataType::List(Arc::new(Field::new("item", data_type, true)));
+        vec![
+            Arc::new(Field::new("winners", list_of(DataType::Int32), false)),
+            Arc::new(Field::new("total_utility", DataType::Float64, false)),
+            Arc::new(Field::new("rank_utility", DataType::Float64, false)),
+            Arc::new(Field::new("cc_coverage", DataType::Float64, false)),
+            Arc::new(Field::new("prop_degree", list_of(DataType::Float64), false)),
+            Arc::new(Field::new("jr", DataType::Boolean, false)),
+            Arc::new(Field::new("ejr", DataType::Boolean, false)),
+            Arc::new(Field::new("iterations", DataType::Int32, true)),
+            Arc::new(Field::new("cycle_detected", DataType::Boolean, true)),
+        ]
+    }
+
+    pub fn data_type(&self) -> DataType {
+        DataType::Struct(Fields::from(Self::fields()))
+    }
+
+    pub fn get_column(&mut self) -> ArrayRef {
+        let arrays = vec![
+            Arc::new(self.winners_bldr.finish()) as ArrayRef,
+            Arc::new(self.total_utility_bldr.finish()) as ArrayRef,
+            Arc::new(self.rank_utility_bldr.finish()) as ArrayRef,
+            Arc::new(self.coverage_bldr.finish()) as ArrayRef,
+            Arc::new(self.prop_degree_bldr.finish()) as ArrayRef,
+            Arc::new(self.jr_bldr.finish()) as ArrayRef,
+            Arc::new(self.ejr_bldr.finish()) as ArrayRef,
+            Arc::new(self.iterations_bldr.finish()) as ArrayRef,
+            Arc::new(self.cycle_detected_bldr.finish()) as ArrayRef,
+        ];
+        let columns: Vec<(FieldRef, ArrayRef)> = Self::fields().into_iter().zip(arrays).collect();
+        Arc::new(StructArray::from(columns))
+    }
+
+    pub fn sendable_report(&self) -> SendableCommitteeReport {
+        SendableCommitteeReport {
+            name: self.colname.clone(),
+            ntrials: self.ntrials,
+            ntrials_jr: self.ntrials_jr,
+            ntrials_ejr: self.ntrials_ejr,
+            mean_total_utility: self.mean_total_utility,
+            mean_rank_utility: self.mean_rank_utility,
+            mean_coverage: self.mean_coverage,
+        }
+    }
+}
+
+pub struct SendableCommitteeReport {
+    pub name: String,
+    pub ntrials: usize,
+    pub ntrials_jr: usize,
+    pub ntrials_ejr: usize,
+    pub mean_total_utility: MeanSD,
+    pub mean_rank_utility: MeanSD,
+    pub mean_coverage: MeanSD,
+}
+
+impl SendableCommitteeReport {
+    pub fn combine(&mut self, other: &Self) {
+        assert!(self.name == other.name);
+        self.ntrials += other.ntrials;
+        self.ntrials_jr += other.ntrials_jr;
+        self.ntrials_ejr += other.ntrials_ejr;
+        self.mean_total_utility += other.mean_total_utility;
+        self.mean_rank_utility += other.mean_rank_utility;
+        self.mean_coverage += other.mean_coverage;
+    }
+
+    pub fn report(&self) {
+        let ntrials = self.ntrials as f64;
+        println!(
+            "Committee {}: Avg total utility: {}, avg rank-discounted utility: {}, avg CC coverage: {}, frac JR: {}, frac EJR: {}, {} elections",
+            self.name,
+            self.mean_total_utility.mean(),
+            self.mean_rank_utility.mean(),
+            self.mean_coverage.mean(),
+            self.ntrials_jr as f64 / ntrials,
+            self.ntrials_ejr as f64 / ntrials,
+            self.ntrials,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::{Borda, Plurality};
+
+    #[test]
+    fn test_prereq_insertion() {
+        let sim = Sim::new(4, 10);
+        let methods = vec![
+            Method::Plurality(Plurality {
+                strat: Strategy::Strategic { fraction: 1.0 },
+            }),
+            Method::Borda(Borda {
+                strat: Strategy::Honest,
+                rank_top_n: None,
+            }),
+            Method::Borda(Borda {
+                strat: Strategy::Strategic { fraction: 1.0 },
+                rank_top_n: None,
+            }),
+            Method::Plurality(Plurality {
+                strat: Strategy::Strategic { fraction: 0.5 },
+            }),
+        ];
+        let trackers = new_method_trackers(&methods, &sim, 1);
+        let summary: Vec<_> = trackers
+            .iter()
+            .map(|t| (t.colname(), t.is_visible(), t.prereq()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("pl_h".to_string(), false, None),
+                ("pl_s".to_string(), true, Some(0)),
+                ("Borda_h".to_string(), true, None),
+                ("Borda_s".to_string(), true, Some(2)),
+                ("pl_s50".to_string(), true, Some(0)),
+            ]
         );
     }
 }
@@ -0,0 +1,154 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::{Array2, ArrayView1};
+use serde::{Deserialize, Serialize};
+
+use super::rangevoting::fill_range_ballots;
+use super::results::WinnerAndRunnerup;
+use super::stv::Quota;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// Allocated Score (STAR-PR) elects the candidate with the highest weighted
+/// total score, then allocates a quota of ballots to them: ballots giving the
+/// winner their top score first, then the next score down, and so on. Allocated
+/// ballots are spent. Where the quota runs out partway through a score, every
+/// ballot giving that score is partly spent, keeping the rest of its weight.
+/// If the winner's supporters fall short of a quota, they are all spent.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AllocatedScore {
+    pub ranks: i32,
+    /// Defaults to Hare.
+    #[serde(default = "default_quota")]
+    pub quota: Quota,
+}
+
+pub(super) fn default_quota() -> Quota {
+    Quota::Hare
+}
+
+pub struct AllocatedScoreSim {
+    p: AllocatedScore,
+    ballots: Array2<i32>, // (cit, cand)
+    weights: Vec<f64>,
+    winners: Vec<ElectResult>,
+}
+
+impl AllocatedScore {
+    pub fn new_sim(&self, sim: &Sim) -> AllocatedScoreSim {
+        AllocatedScoreSim {
+            p: self.clone(),
+            ballots: Array2::zeros((sim.ncit, sim.ncand)),
+            weights: vec![1.0; sim.ncit],
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl MWMethodSim for AllocatedScoreSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, self.p.ranks, &mut self.ballots);
+        self.weights.fill(1.0);
+        self.winners.clear();
+        let quota = self.p.quota.votes(sim.ncit as f64, nwinners);
+        let mut remaining: Vec<usize> = (0..sim.ncand).collect();
+        while self.winners.len() < nwinners && !remaining.is_empty() {
+            let mut best = 0;
+            let mut best_score = f64::MIN;
+            for (idx, &icand) in remaining.iter().enumerate() {
+                let score: f64 = self
+                    .ballots
+                    .column(icand)
+                    .iter()
+                    .zip(self.weights.iter())
+                    .map(|(&s, w)| s as f64 * w)
+                    .sum();
+                if score > best_score {
+                    best = idx;
+                    best_score = score;
+                }
+            }
+            let winner = remaining.remove(best);
+            allocate(
+                self.ballots.column(winner),
+                self.p.ranks,
+                &mut self.weights,
+                quota,
+            );
+            self.winners.push(ElectResult {
+                cand: winner,
+                score: best_score,
+            });
+        }
+        &self.winners
+    }
+}
+
+/// Spends a quota of ballot weight on the ballots giving the highest scores.
+fn allocate(scores: ArrayView1<i32>, ranks: i32, weights: &mut [f64], quota: f64) {
+    let mut needed = quota;
+    for level in (1..ranks).rev() {
+        let at_level: f64 = scores
+            .iter()
+            .zip(weights.iter())
+            .filter(|(&s, _)| s == level)
+            .map(|(_, w)| w)
+            .sum();
+        let kept = if at_level <= needed {
+            0.0
+        } else {
+            1.0 - needed / at_level
+        };
+        for (_, w) in scores
+            .iter()
+            .zip(weights.iter_mut())
+            .filter(|(&s, _)| s == level)
+        {
+            *w *= kept;
+        }
+        needed -= at_level;
+        if needed <= 0.0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_allocated_score() {
+        let sim = sim_from_scores(&[(&[4., 4., 0.], 7), (&[0., 0., 4.], 3)]);
+        let mut allocated = AllocatedScore {
+            ranks: 5,
+            quota: Quota::Hare,
+        }
+        .new_sim(&sim);
+        let results = allocated.multi_elect(&sim, None, 3);
+        // The quota is 10/3. Candidate 0 spends 10/21 of each majority ballot,
+        // leaving 11/21 to elect candidate 1 with 7 * 4 * 11/21 points.
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        assert_eq!(winners, vec![0, 1, 2]);
+        assert_float_eq!(results[0].score, 28.0, abs <= 1.0e-12);
+        assert_float_eq!(results[1].score, 44.0 / 3.0, abs <= 1.0e-12);
+        assert_float_eq!(results[2].score, 12.0, abs <= 1.0e-12);
+
+        // Top scores are spent before lower ones.
+        let scores = ndarray::array![4, 4, 3, 3, 1, 0];
+        let mut weights = vec![1.0; 6];
+        allocate(scores.view(), 5, &mut weights, 3.0);
+        assert_eq!(weights, vec![0.0, 0.0, 0.5, 0.5, 1.0, 1.0]);
+        allocate(scores.view(), 5, &mut weights, 3.0);
+        assert_eq!(weights, vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+}
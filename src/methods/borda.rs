@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use super::results::{default_honest, Strategy, WinnerAndRunnerup};
 use super::tallies::{tally_votes, Tallies};
-use super::MethodSim;
+use super::{Method, MethodSim};
 use crate::sim::Sim;
 
 /// The Borda Count method is a *ranked* voting method.
@@ -69,41 +69,36 @@ impl MethodSim for BordaSim {
         } else {
             sim.ncand
         };
-        match self.p.strat {
-            Strategy::Honest => {
-                for cand_fav_list in sim.ranks.lanes(Axis(1)) {
-                    for (cand_rank, &icand) in cand_fav_list.indexed_iter().take(top_ncand) {
-                        self.tallies[icand] += (top_ncand - cand_rank) as i32;
-                    }
-                }
-            }
-            Strategy::Strategic => {
+        for (icit, cand_fav_list) in sim.ranks.lanes(Axis(1)).into_iter().enumerate() {
+            if sim.is_strategic(icit, self.p.strat) {
                 // Note strategic scoring is reduced by 1 so that enemy == 0 always.
-                for (icit, cand_fav_list) in sim.ranks.lanes(Axis(1)).into_iter().enumerate() {
-                    let pre_elect = honest_rslt.unwrap();
-                    let (friend, enemy) = if sim.scores[(icit, pre_elect.winner.cand)]
-                        >= sim.scores[(icit, pre_elect.runnerup.cand)]
-                    {
-                        (pre_elect.winner.cand, pre_elect.runnerup.cand)
-                    } else {
-                        (pre_elect.runnerup.cand, pre_elect.winner.cand)
-                    };
-                    let mut score_shift: i32 = -2; // leave room for friend to score max
-                    for (cand_rank, &icand) in cand_fav_list.indexed_iter() {
-                        if icand == friend {
-                            self.tallies[icand] += top_ncand as i32 - 1; // Score friend the highest
-                            score_shift += 1;
-                        } else if icand == enemy {
-                            score_shift += 1;
-                        } else if (top_ncand - cand_rank) as i32 + score_shift > 0 {
-                            self.tallies[icand] += (top_ncand - cand_rank) as i32 + score_shift;
-                        }
+                let pre_elect = honest_rslt.unwrap();
+                let (friend, enemy) = if sim.scores[(icit, pre_elect.winner.cand)]
+                    >= sim.scores[(icit, pre_elect.runnerup.cand)]
+                {
+                    (pre_elect.winner.cand, pre_elect.runnerup.cand)
+                } else {
+                    (pre_elect.runnerup.cand, pre_elect.winner.cand)
+                };
+                let mut score_shift: i32 = -2; // leave room for friend to score max
+                for (cand_rank, &icand) in cand_fav_list.indexed_iter() {
+                    if icand == friend {
+                        self.tallies[icand] += top_ncand as i32 - 1; // Score friend the highest
+                        score_shift += 1;
+                    } else if icand == enemy {
+                        score_shift += 1;
+                    } else if (top_ncand - cand_rank) as i32 + score_shift > 0 {
+                        self.tallies[icand] += (top_ncand - cand_rank) as i32 + score_shift;
                     }
                 }
+            } else {
+                for (cand_rank, &icand) in cand_fav_list.indexed_iter().take(top_ncand) {
+                    self.tallies[icand] += (top_ncand - cand_rank) as i32;
+                }
             }
         }
         log::info!("Borda tallies are: {:?}", self.tallies);
-        tally_votes(&self.tallies)
+        tally_votes(&self.tallies, sim)
     }
 
     fn name(&self) -> String {
@@ -121,8 +116,21 @@ impl MethodSim for BordaSim {
     }
 
     fn strat(&self) -> Strategy {
-        // self.p.strat
-        Strategy::Honest
+        self.p.strat
+    }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!("points: {:?}", self.tallies)]
+    }
+
+    fn strategic_prereq(&self) -> Option<Method> {
+        match self.p.strat {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => Some(Method::Borda(Borda {
+                strat: Strategy::Honest,
+                ..self.p.clone()
+            })),
+        }
     }
 }
 
@@ -155,7 +163,7 @@ mod tests {
         assert_eq!(honest_results.runnerup.score, 14.);
 
         let mut method = Borda {
-            strat: Strategy::Strategic,
+            strat: Strategy::Strategic { fraction: 1.0 },
             rank_top_n: None,
         }
         .new_sim(&sim);
@@ -193,7 +201,7 @@ mod tests {
         assert_eq!(honest_results.runnerup.cand, 0);
 
         let mut method = Borda {
-            strat: Strategy::Strategic,
+            strat: Strategy::Strategic { fraction: 1.0 },
             rank_top_n: Some(3),
         }
         .new_sim(&sim);
@@ -19,6 +19,8 @@ pub struct BtrIrvSim {
     _p: BtrIrv,
     tallies: Tallies,
     eliminated: Vec<bool>,
+    round_tallies: Vec<i32>, // Tallies of every round, one after another
+    eliminations: Vec<usize>,
     candidates: Vec<usize>,
 }
 
@@ -28,6 +30,8 @@ impl BtrIrv {
             _p: self.clone(),
             tallies: vec![0; sim.ncand],
             eliminated: vec![false; sim.ncand],
+            round_tallies: Vec::with_capacity(sim.ncand * sim.ncand),
+            eliminations: Vec::with_capacity(sim.ncand),
             candidates: Vec::with_capacity(sim.ncand),
         }
     }
@@ -36,6 +40,8 @@ impl BtrIrv {
 impl MethodSim for BtrIrvSim {
     fn elect(&mut self, sim: &Sim, _honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
         self.eliminated.fill(false);
+        self.round_tallies.clear();
+        self.eliminations.clear();
         loop {
             log::debug!("IRV round: eliminated = {:?}", self.eliminated);
             // Tally up the votes -- each voter's favorite non-eliminated candidate gets a tally.
@@ -49,6 +55,7 @@ impl MethodSim for BtrIrvSim {
                 }
             }
             log::debug!("  tallies are: {:?}", self.tallies);
+            self.round_tallies.extend_from_slice(&self.tallies);
 
             self.candidates.clear();
             self.candidates.extend(
@@ -88,6 +95,7 @@ impl MethodSim for BtrIrvSim {
             }
             log::debug!("top_cand = {}, bot_cand = {}", top_cand, bot_cand);
             self.eliminated[bot_cand] = true;
+            self.eliminations.push(bot_cand);
         }
     }
 
@@ -107,6 +115,17 @@ impl MethodSim for BtrIrvSim {
         // self.p.strat
         Strategy::Honest
     }
+
+    fn explain(&self, sim: &Sim) -> Vec<String> {
+        self.round_tallies
+            .chunks(sim.ncand)
+            .enumerate()
+            .map(|(iround, tallies)| match self.eliminations.get(iround) {
+                Some(icand) => format!("round {}: {:?}, eliminated {}", iround + 1, tallies, icand),
+                None => format!("round {}: {:?}", iround + 1, tallies),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
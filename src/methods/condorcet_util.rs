@@ -36,6 +36,25 @@ pub fn find_candidate_pairoffs(pairs: &mut Vec<CandPair>, sim: &Sim) {
     }
 }
 
+/// count_pairwise_preferences fills prefs[(i, j)] with the number of voters who
+/// score candidate i strictly higher than candidate j. Sim::i_beats_j_by only keeps
+/// the margin, which loses the size of the winning side when some voters score
+/// a pair equally.
+pub fn count_pairwise_preferences(prefs: &mut Array2<i32>, sim: &Sim) {
+    prefs.fill(0);
+    for cit_scores in sim.scores.outer_iter() {
+        for icand in 0..sim.ncand {
+            for jcand in 0..icand {
+                if cit_scores[icand] > cit_scores[jcand] {
+                    prefs[(icand, jcand)] += 1;
+                } else if cit_scores[icand] < cit_scores[jcand] {
+                    prefs[(jcand, icand)] += 1;
+                }
+            }
+        }
+    }
+}
+
 pub fn lock_in(locked_in: &mut Array2<bool>, pair: &CandPair, set: bool) {
     if pair.winner > pair.loser {
         locked_in[(pair.loser, pair.winner)] = set; // (i, j) where j > i
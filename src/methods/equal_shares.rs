@@ -0,0 +1,235 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::rangevoting::fill_range_ballots;
+use super::results::WinnerAndRunnerup;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// The Method of Equal Shares, on range ballots (approval ballots with the
+/// default of two ranks). Every voter starts with an equal share of a budget of
+/// one unit per seat. A candidate costs one unit, paid by their supporters in
+/// proportion to their scores, except that voters short of their share pay
+/// all they have left. The next winner is the candidate whose supporters pay
+/// the least per point of score, and this continues until no candidate is
+/// affordable.
+///
+/// That usually leaves seats empty, so the standard completion follows: the
+/// whole election is rerun with the total budget raised one unit at a time, as
+/// long as that doesn't elect more candidates than there are seats. Any seats
+/// still empty go to the remaining candidates with the highest total scores.
+///
+/// Each winner's score is their price per point of score, so lower scores were
+/// elected more easily. Winners seated by total score have infinite scores.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MES {
+    #[serde(default = "default_ranks")]
+    pub ranks: i32,
+}
+
+fn default_ranks() -> i32 {
+    2
+}
+
+pub struct MESSim {
+    p: MES,
+    ballots: Array2<i32>, // (cit, cand)
+    budgets: Vec<f64>,
+    winners: Vec<ElectResult>,
+}
+
+impl MES {
+    pub fn new_sim(&self, sim: &Sim) -> MESSim {
+        MESSim {
+            p: self.clone(),
+            ballots: Array2::zeros((sim.ncit, sim.ncand)),
+            budgets: vec![0.0; sim.ncit],
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl MWMethodSim for MESSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, self.p.ranks, &mut self.ballots);
+        let nwinners = nwinners.min(sim.ncand);
+        let nsupported = (0..sim.ncand)
+            .filter(|&icand| self.ballots.column(icand).iter().any(|&s| s > 0))
+            .count();
+
+        // Raise the budget until the seats are full, or would be overfilled,
+        // or every candidate anyone supports is already elected.
+        let mut budget = nwinners as f64;
+        self.winners = equal_shares(&self.ballots, &mut self.budgets, budget, nwinners);
+        while self.winners.len() < nwinners.min(nsupported) {
+            budget += 1.0;
+            let winners = equal_shares(&self.ballots, &mut self.budgets, budget, nwinners);
+            if winners.len() > nwinners {
+                budget -= 1.0;
+                break;
+            }
+            self.winners = winners;
+        }
+        log::debug!(
+            "Equal shares elected {} of {} with a budget of {}",
+            self.winners.len(),
+            nwinners,
+            budget
+        );
+
+        // Utilitarian completion
+        let mut remaining: Vec<usize> = (0..sim.ncand)
+            .filter(|icand| !self.winners.iter().any(|w| w.cand == *icand))
+            .collect();
+        while self.winners.len() < nwinners {
+            let mut best = 0;
+            let mut best_total = -1;
+            for (idx, &icand) in remaining.iter().enumerate() {
+                let total: i32 = self.ballots.column(icand).sum();
+                if total > best_total {
+                    best = idx;
+                    best_total = total;
+                }
+            }
+            self.winners.push(ElectResult {
+                cand: remaining.remove(best),
+                score: f64::INFINITY,
+            });
+        }
+        &self.winners
+    }
+}
+
+/// Runs equal shares with a total budget split evenly among voters, stopping
+/// early once more than nwinners are elected.
+fn equal_shares(
+    ballots: &Array2<i32>,
+    budgets: &mut [f64],
+    budget: f64,
+    nwinners: usize,
+) -> Vec<ElectResult> {
+    let (ncit, ncand) = ballots.dim();
+    budgets.fill(budget / ncit as f64);
+    let mut remaining: Vec<usize> = (0..ncand).collect();
+    let mut winners = Vec::with_capacity(nwinners + 1);
+    while winners.len() <= nwinners {
+        let mut best = None;
+        let mut best_price = f64::INFINITY;
+        for (idx, &icand) in remaining.iter().enumerate() {
+            if let Some(price) = price_per_point(ballots, budgets, icand) {
+                if price < best_price {
+                    best = Some(idx);
+                    best_price = price;
+                }
+            }
+        }
+        let Some(best) = best else {
+            break;
+        };
+        let winner = remaining.remove(best);
+        for (icit, b) in budgets.iter_mut().enumerate() {
+            let score = ballots[(icit, winner)] as f64;
+            *b -= b.min(best_price * score);
+        }
+        winners.push(ElectResult {
+            cand: winner,
+            score: best_price,
+        });
+    }
+    winners
+}
+
+/// The smallest price per point of score at which the candidate's supporters
+/// can pay one unit between them, each paying at most what they have left.
+/// None if they can't afford it.
+fn price_per_point(ballots: &Array2<i32>, budgets: &[f64], icand: usize) -> Option<f64> {
+    // Supporters as (price at which they run out, score)
+    let mut supporters: Vec<(f64, f64)> = ballots
+        .column(icand)
+        .iter()
+        .zip(budgets.iter())
+        .filter(|(&score, _)| score > 0)
+        .map(|(&score, &b)| (b / score as f64, score as f64))
+        .collect();
+    let funds: f64 = supporters.iter().map(|(limit, score)| limit * score).sum();
+    if funds < 1.0 - 1.0e-9 {
+        return None;
+    }
+    supporters.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut cost = 1.0;
+    let mut points: f64 = supporters.iter().map(|(_, score)| score).sum();
+    for (limit, score) in supporters.iter() {
+        // Everyone left pays the price for each point, if they can.
+        let price = cost / points;
+        if price <= *limit {
+            return Some(price);
+        }
+        cost -= limit * score;
+        points -= score;
+    }
+    // Supporters' funds cover the cost only to rounding.
+    supporters.last().map(|(limit, _)| *limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_equal_shares() {
+        // Six voters approve candidates 0-2, and three approve 3-4.
+        let sim = sim_from_scores(&[(&[1., 1., 1., 0., 0.], 6), (&[0., 0., 0., 1., 1.], 3)]);
+        let mut mes = MES { ranks: 2 }.new_sim(&sim);
+        let results = mes.multi_elect(&sim, None, 3);
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        // Each voter has 1/3. A majority candidate costs 1/6 each, and a minority
+        // one 1/3 each, so the majority can afford two and the minority one.
+        assert_eq!(winners, vec![0, 1, 3]);
+        assert_float_eq!(results[0].score, 1.0 / 6.0, abs <= 1.0e-12);
+        assert_float_eq!(results[2].score, 1.0 / 3.0, abs <= 1.0e-12);
+    }
+
+    #[test]
+    fn test_equal_shares_completion() {
+        let sim = sim_from_scores(&[(&[1., 0., 0.], 3), (&[0., 1., 0.], 3), (&[0., 0., 1.], 4)]);
+        let mut ballots = Array2::zeros((10, 3));
+        fill_range_ballots(&sim, 2, &mut ballots);
+        let mut budgets = vec![0.0; 10];
+        // With 0.2 each, no group can afford its candidate. With 0.3, only the
+        // largest can. With 0.4, every group can, electing one too many.
+        assert!(equal_shares(&ballots, &mut budgets, 2.0, 2).is_empty());
+        assert_eq!(equal_shares(&ballots, &mut budgets, 3.0, 2).len(), 1);
+        assert_eq!(equal_shares(&ballots, &mut budgets, 4.0, 2).len(), 3);
+
+        // So 2 wins at 0.25 each, and 0 is seated by total score, ahead of 1.
+        let mut mes = MES { ranks: 2 }.new_sim(&sim);
+        let results = mes.multi_elect(&sim, None, 2);
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        assert_eq!(winners, vec![2, 0]);
+        assert_eq!(results[0].score, 0.25);
+        assert_eq!(results[1].score, f64::INFINITY);
+
+        // Cardinal ballots pay in proportion to their scores.
+        let ballots = ndarray::array![[2], [1]];
+        let price = price_per_point(&ballots, &[1.0, 1.0], 0).unwrap();
+        assert_float_eq!(price, 1.0 / 3.0, abs <= 1.0e-12);
+
+        // A voter whose budget runs out pays what they have, and the others
+        // cover the rest.
+        let ballots = ndarray::array![[1], [1], [1]];
+        let price = price_per_point(&ballots, &[0.1, 0.5, 0.5], 0).unwrap();
+        assert_float_eq!(price, 0.45, abs <= 1.0e-12);
+        assert_eq!(price_per_point(&ballots, &[0.1, 0.4, 0.4], 0), None);
+    }
+}
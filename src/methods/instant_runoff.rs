@@ -20,6 +20,8 @@ pub struct IRVSim {
     _p: InstantRunoff,
     tallies: Tallies,
     eliminated: Vec<bool>,
+    round_tallies: Vec<i32>, // Tallies of every round, one after another
+    eliminations: Vec<usize>,
 }
 
 impl InstantRunoff {
@@ -28,6 +30,8 @@ impl InstantRunoff {
             _p: self.clone(),
             tallies: vec![0; sim.ncand],
             eliminated: vec![false; sim.ncand],
+            round_tallies: Vec::with_capacity(sim.ncand * sim.ncand),
+            eliminations: Vec::with_capacity(sim.ncand),
         }
     }
 }
@@ -35,6 +39,8 @@ impl InstantRunoff {
 impl MethodSim for IRVSim {
     fn elect(&mut self, sim: &Sim, _honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
         self.eliminated.fill(false);
+        self.round_tallies.clear();
+        self.eliminations.clear();
         loop {
             debug!("IRV round: eliminated = {:?}", self.eliminated);
             // Tally up the votes -- each voter's favorite non-eliminated candidate gets a tally.
@@ -48,6 +54,7 @@ impl MethodSim for IRVSim {
                 }
             }
             debug!("  tallies are: {:?}", self.tallies);
+            self.round_tallies.extend_from_slice(&self.tallies);
 
             // Find top and bottom candidates
             let mut top_cand = sim.ncand; // invalid index
@@ -93,6 +100,7 @@ impl MethodSim for IRVSim {
                 };
             } else {
                 self.eliminated[bot_cand] = true;
+                self.eliminations.push(bot_cand);
             }
         }
     }
@@ -113,6 +121,17 @@ impl MethodSim for IRVSim {
         // self.p.strat
         Strategy::Honest
     }
+
+    fn explain(&self, sim: &Sim) -> Vec<String> {
+        self.round_tallies
+            .chunks(sim.ncand)
+            .enumerate()
+            .map(|(iround, tallies)| match self.eliminations.get(iround) {
+                Some(icand) => format!("round {}: {:?}, eliminated {}", iround + 1, tallies, icand),
+                None => format!("round {}: {:?}", iround + 1, tallies),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -141,5 +160,13 @@ mod tests {
         assert_eq!(honest_results.winner.score, 3.);
         assert_eq!(honest_results.runnerup.cand, 1);
         assert_eq!(honest_results.runnerup.score, 2.);
+        assert_eq!(
+            method.explain(&sim),
+            vec![
+                "round 1: [2, 2, 0, 1], eliminated 2",
+                "round 2: [2, 2, 0, 1], eliminated 3",
+                "round 3: [3, 2, 0, 0]",
+            ]
+        );
     }
 }
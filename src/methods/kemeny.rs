@@ -0,0 +1,361 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::results::{ElectResult, Strategy, WinnerAndRunnerup};
+use super::MethodSim;
+use crate::sim::Sim;
+
+/// The Kemeny-Young method finds the complete ranking of candidates that agrees
+/// with the most pairwise preferences expressed by voters. For every pair of
+/// candidates, a ranking earns the number of voters who order that pair the same way.
+/// The winner is the top of the best ranking. It is a Condorcet method, and
+/// the consensus ranking makes it a useful reference for other Condorcet methods.
+///
+/// Finding the best ranking is NP-hard. A local search always runs first, and a
+/// branch-and-bound search then tries to prove the result optimal. With many
+/// candidates the branch-and-bound search can be cut short, in which case the
+/// local search (or the best ranking found so far) is used unproven.
+///
+/// The winner and runner-up are scored by the number of candidates ranked below them.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Kemeny {
+    /// With this many candidates or fewer, the branch-and-bound search is never
+    /// cut short, so the result is always exact.
+    #[serde(default = "default_exact_max_cands")]
+    pub exact_max_cands: usize,
+    /// With more than `exact_max_cands` candidates, the branch-and-bound search
+    /// gives up after visiting this many nodes. Zero means local search only.
+    #[serde(default = "default_node_limit")]
+    pub node_limit: usize,
+}
+
+fn default_exact_max_cands() -> usize {
+    7
+}
+
+fn default_node_limit() -> usize {
+    100_000
+}
+
+#[derive(Debug)]
+pub struct KemenySim {
+    params: Kemeny,
+    ranking: Vec<usize>, // Best ranking found, favorite first
+    best_score: i64,
+    proven: bool,
+    // Branch-and-bound state
+    prefix: Vec<usize>,
+    remaining: Vec<bool>,
+    nodes: usize,
+    node_limit: usize,
+}
+
+impl Kemeny {
+    pub fn new_sim(&self, sim: &Sim) -> KemenySim {
+        KemenySim {
+            params: self.clone(),
+            ranking: (0..sim.ncand).collect(),
+            best_score: 0,
+            proven: false,
+            prefix: Vec::with_capacity(sim.ncand),
+            remaining: vec![true; sim.ncand],
+            nodes: 0,
+            node_limit: 0,
+        }
+    }
+}
+
+/// Scores a ranking by summing the margins of every pair it orders. This differs
+/// from the count of agreeing voters by a constant, so both have the same optimum.
+fn ranking_score(ranking: &[usize], margins: &Array2<i32>) -> i64 {
+    let mut score = 0;
+    for (pos, &a) in ranking.iter().enumerate() {
+        for &b in ranking[pos + 1..].iter() {
+            score += margins[(a, b)] as i64;
+        }
+    }
+    score
+}
+
+impl MethodSim for KemenySim {
+    fn elect(&mut self, sim: &Sim, _honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
+        let margins = &sim.i_beats_j_by;
+        self.local_search(margins);
+        log::debug!(
+            "Kemeny local search: {:?}, score {}",
+            self.ranking,
+            self.best_score
+        );
+
+        self.node_limit = if sim.ncand <= self.params.exact_max_cands {
+            usize::MAX
+        } else {
+            self.params.node_limit
+        };
+        self.proven = if self.node_limit > 0 {
+            self.nodes = 0;
+            self.prefix.clear();
+            self.remaining.fill(true);
+            let mut pair_bound = 0;
+            for i in 0..sim.ncand {
+                for j in (i + 1)..sim.ncand {
+                    pair_bound += margins[(i, j)].abs() as i64;
+                }
+            }
+            let completed = self.branch_and_bound(margins, 0, pair_bound);
+            log::debug!(
+                "Kemeny branch and bound: {:?}, score {}, {} nodes, proven {}",
+                self.ranking,
+                self.best_score,
+                self.nodes,
+                completed
+            );
+            completed
+        } else {
+            false
+        };
+
+        let (winner, runner_up) = (self.ranking[0], self.ranking[1]);
+        WinnerAndRunnerup {
+            winner: ElectResult {
+                cand: winner,
+                score: (sim.ncand - 1) as f64,
+            },
+            runnerup: ElectResult {
+                cand: runner_up,
+                score: (sim.ncand - 2) as f64,
+            },
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("Kemeny-Young, {}", Strategy::Honest)
+    }
+
+    fn colname(&self) -> String {
+        "kemeny_h".to_string()
+    }
+
+    fn strat(&self) -> Strategy {
+        Strategy::Honest
+    }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!(
+            "best ranking: {:?}, score {}, proven optimal: {}",
+            self.ranking, self.best_score, self.proven
+        )]
+    }
+
+    fn proven_optimal(&self) -> Option<bool> {
+        Some(self.proven)
+    }
+}
+
+impl KemenySim {
+    /// Starts from candidates ordered by their total margin, then moves single
+    /// candidates up or down the ranking while that improves the score.
+    fn local_search(&mut self, margins: &Array2<i32>) {
+        let ncand = self.ranking.len();
+        self.ranking.clear();
+        self.ranking.extend(0..ncand);
+        self.ranking
+            .sort_by_key(|&a| -(0..ncand).map(|b| margins[(a, b)]).sum::<i32>());
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for from in 0..ncand {
+                let x = self.ranking[from];
+                // Moving x past a candidate y flips that pair, changing the score
+                // by twice the margin of the candidate being passed.
+                let mut best_delta = 0;
+                let mut best_to = from;
+                let mut delta = 0;
+                for to in (0..from).rev() {
+                    delta += 2 * margins[(x, self.ranking[to])];
+                    if delta > best_delta {
+                        best_delta = delta;
+                        best_to = to;
+                    }
+                }
+                delta = 0;
+                for to in (from + 1)..ncand {
+                    delta += 2 * margins[(self.ranking[to], x)];
+                    if delta > best_delta {
+                        best_delta = delta;
+                        best_to = to;
+                    }
+                }
+                if best_to != from {
+                    self.ranking.remove(from);
+                    self.ranking.insert(best_to, x);
+                    improved = true;
+                }
+            }
+        }
+        self.best_score = ranking_score(&self.ranking, margins);
+    }
+
+    /// Extends self.prefix one candidate at a time. pair_bound is the sum of
+    /// absolute margins among remaining candidates, an upper bound on what they can
+    /// still add. Returns false if the node limit was hit.
+    fn branch_and_bound(&mut self, margins: &Array2<i32>, score: i64, pair_bound: i64) -> bool {
+        let ncand = self.remaining.len();
+        if self.prefix.len() == ncand {
+            if score > self.best_score {
+                self.best_score = score;
+                self.ranking.clone_from(&self.prefix);
+            }
+            return true;
+        }
+
+        // Placing c next settles its pairs with every other remaining candidate.
+        let mut children: Vec<(i64, i64, usize)> = Vec::with_capacity(ncand);
+        for c in (0..ncand).filter(|&c| self.remaining[c]) {
+            let mut gain = 0;
+            let mut c_bound = 0;
+            for r in (0..ncand).filter(|&r| r != c && self.remaining[r]) {
+                gain += margins[(c, r)] as i64;
+                c_bound += margins[(c, r)].abs() as i64;
+            }
+            children.push((gain, pair_bound - c_bound, c));
+        }
+        // Most promising first, so that good rankings prune the rest sooner.
+        children.sort_by_key(|&(gain, _, _)| -gain);
+
+        for (gain, rest_bound, c) in children {
+            if score + gain + rest_bound <= self.best_score {
+                continue;
+            }
+            self.nodes += 1;
+            if self.nodes > self.node_limit {
+                return false;
+            }
+            self.remaining[c] = false;
+            self.prefix.push(c);
+            let completed = self.branch_and_bound(margins, score + gain, rest_bound);
+            self.prefix.pop();
+            self.remaining[c] = true;
+            if !completed {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+
+    fn best_score_exhaustive(
+        prefix: &mut Vec<usize>,
+        remaining: &mut Vec<bool>,
+        margins: &Array2<i32>,
+    ) -> i64 {
+        if prefix.len() == remaining.len() {
+            return ranking_score(prefix, margins);
+        }
+        let mut best = i64::MIN;
+        for c in 0..remaining.len() {
+            if remaining[c] {
+                remaining[c] = false;
+                prefix.push(c);
+                best = best.max(best_score_exhaustive(prefix, remaining, margins));
+                prefix.pop();
+                remaining[c] = true;
+            }
+        }
+        best
+    }
+
+    fn scrambled_sim(ncand: usize, ncit: usize) -> Sim {
+        let mut sim = Sim::new(ncand, ncit);
+        for ((icit, icand), score) in sim.scores.indexed_iter_mut() {
+            *score = ((icit * 101 + icand * 37 + icit * icand * 53) % 1009) as f64;
+        }
+        sim.rank_candidates();
+        sim
+    }
+
+    #[test]
+    fn test_kemeny() {
+        // Example from https://electowiki.org/wiki/Kemeny-Young_method
+        // Memphis, Nashville, Chattanooga, Knoxville
+        let mut sim = sim_from_scores(&[
+            (&[-1., -2., -3., -4.], 42),
+            (&[-4., -1., -2., -3.], 26),
+            (&[-4., -3., -1., -2.], 15),
+            (&[-4., -3., -2., -1.], 17),
+        ]);
+        sim.rank_candidates();
+        let mut method = Kemeny {
+            exact_max_cands: 7,
+            node_limit: 0,
+        }
+        .new_sim(&sim);
+        let results = method.elect(&sim, None);
+        // Nashville > Chattanooga > Knoxville > Memphis, with 393 agreeing preferences.
+        assert_eq!(method.ranking, vec![1, 2, 3, 0]);
+        assert_eq!(method.best_score, 2 * 393 - 6 * 100);
+        assert_eq!(method.proven_optimal(), Some(true));
+        assert_eq!(results.winner.cand, 1);
+        assert_eq!(results.runnerup.cand, 2);
+    }
+
+    #[test]
+    fn test_kemeny_search_modes() {
+        let sim = scrambled_sim(7, 41);
+        let exact = best_score_exhaustive(
+            &mut Vec::new(),
+            &mut vec![true; sim.ncand],
+            &sim.i_beats_j_by,
+        );
+        let mut method = Kemeny {
+            exact_max_cands: 7,
+            node_limit: 0,
+        }
+        .new_sim(&sim);
+        method.elect(&sim, None);
+        assert_eq!(method.best_score, exact);
+        assert_eq!(
+            method.best_score,
+            ranking_score(&method.ranking, &sim.i_beats_j_by)
+        );
+        assert!(method.proven);
+
+        // Local search only is never proven.
+        let mut method = Kemeny {
+            exact_max_cands: 4,
+            node_limit: 0,
+        }
+        .new_sim(&sim);
+        method.elect(&sim, None);
+        assert!(method.best_score <= exact);
+        assert!(!method.proven);
+
+        // A node limit that is too small gives up. All 10 candidates are in the Smith set.
+        let sim = scrambled_sim(10, 9);
+        let mut method = Kemeny {
+            exact_max_cands: 7,
+            node_limit: 5,
+        }
+        .new_sim(&sim);
+        method.elect(&sim, None);
+        assert!(!method.proven);
+
+        // A generous one does not.
+        let mut method = Kemeny {
+            exact_max_cands: 7,
+            node_limit: 100_000_000,
+        }
+        .new_sim(&sim);
+        method.elect(&sim, None);
+        assert!(method.proven);
+    }
+}
@@ -71,6 +71,13 @@ impl MethodSim for MinimaxSim {
     fn strat(&self) -> Strategy {
         Strategy::Honest
     }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!(
+            "worst pairwise margins: {:?}",
+            self.min_victory_margin
+        )]
+    }
 }
 
 #[cfg(test)]
@@ -1,32 +1,48 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
+mod allocated_score;
 mod borda;
 mod btr_irv;
 pub mod condorcet_util;
+mod equal_shares;
 mod instant_runoff;
+mod kemeny;
 mod minimax;
 mod multivote;
+mod pav;
+mod phragmen;
 mod plurality;
 mod plurality_top_n;
 mod rangevoting;
 mod ranked_pairs;
 mod results;
 mod reweighted_range;
+mod schulze;
+mod spent_score;
 mod star;
+mod stv;
 mod tallies;
 mod test_utils;
 
+pub use allocated_score::AllocatedScore;
 pub use borda::Borda;
+pub use equal_shares::MES;
 pub use instant_runoff::InstantRunoff;
+pub use kemeny::Kemeny;
 pub use multivote::Multivote;
+pub use pav::{PAV, SPAV};
+pub use phragmen::Phragmen;
 pub use plurality::Plurality;
 pub use plurality_top_n::PluralityTopN;
-pub use rangevoting::RangeVoting;
+pub use rangevoting::{fill_range_ballots, RangeVoting};
 pub use ranked_pairs::RP;
 pub use results::{ElectResult, Strategy, WinnerAndRunnerup};
-pub use reweighted_range::RRV;
+pub use reweighted_range::{IterativeRRV, RRV};
+pub use schulze::Schulze;
+pub use spent_score::SSS;
 pub use star::STAR;
+pub use stv::STV;
 
 use crate::sim::Sim;
 use serde::{Deserialize, Serialize};
@@ -42,6 +58,8 @@ pub enum Method {
     RP(RP),
     BtrIrv(btr_irv::BtrIrv),
     MM(minimax::Minimax),
+    Schulze(Schulze),
+    Kemeny(Kemeny),
 }
 
 impl Method {
@@ -56,6 +74,26 @@ impl Method {
             Method::RP(m) => Box::new(m.new_sim(sim)),
             Method::BtrIrv(m) => Box::new(m.new_sim(sim)),
             Method::MM(m) => Box::new(m.new_sim(sim)),
+            Method::Schulze(m) => Box::new(m.new_sim(sim)),
+            Method::Kemeny(m) => Box::new(m.new_sim(sim)),
+        }
+    }
+
+    /// How voters cast ballots under this method. Methods without a strategic
+    /// variant are always honest.
+    pub fn strat(&self) -> Strategy {
+        match self {
+            Method::Plurality(m) => m.strat,
+            Method::Range(m) => m.strat,
+            Method::Borda(m) => m.strat,
+            Method::Multivote(m) => m.strat,
+            Method::STAR(m) => m.strat,
+            Method::RP(m) => m.strat,
+            Method::InstantRunoff(_)
+            | Method::BtrIrv(_)
+            | Method::MM(_)
+            | Method::Schulze(_)
+            | Method::Kemeny(_) => Strategy::Honest,
         }
     }
 }
@@ -65,20 +103,69 @@ pub trait MethodSim {
     fn name(&self) -> String;
     fn colname(&self) -> String;
     fn strat(&self) -> Strategy;
+    /// Methods that may settle for a heuristic result report whether the last
+    /// winner was proven optimal. Those that return Some must always do so,
+    /// because it adds a field to their output column.
+    fn proven_optimal(&self) -> Option<bool> {
+        None
+    }
+    /// Strategic methods that base their ballots on a pre-election poll return
+    /// the honest method to run as that poll.
+    fn strategic_prereq(&self) -> Option<Method> {
+        None
+    }
+    /// Describes how the last election was decided, for the explain command.
+    /// Candidates are numbered as in sim.
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MultiWinMethod {
     RRV(RRV),
+    IterativeRRV(IterativeRRV),
     PluralityTopN(PluralityTopN),
+    STV(STV),
+    PAV(PAV),
+    SPAV(SPAV),
+    Phragmen(Phragmen),
+    MES(MES),
+    AllocatedScore(AllocatedScore),
+    SSS(SSS),
 }
 
 impl MultiWinMethod {
     pub fn new_sim(&self, sim: &Sim) -> Box<dyn MWMethodSim> {
         match self {
             MultiWinMethod::RRV(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::IterativeRRV(m) => Box::new(m.new_sim(sim)),
             MultiWinMethod::PluralityTopN(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::STV(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::PAV(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::SPAV(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::Phragmen(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::MES(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::AllocatedScore(m) => Box::new(m.new_sim(sim)),
+            MultiWinMethod::SSS(m) => Box::new(m.new_sim(sim)),
+        }
+    }
+
+    /// The variant name, for output columns and reports.
+    pub fn name(&self) -> String {
+        match self {
+            MultiWinMethod::RRV(_) => "RRV",
+            MultiWinMethod::IterativeRRV(_) => "IterativeRRV",
+            MultiWinMethod::PluralityTopN(_) => "PluralityTopN",
+            MultiWinMethod::STV(_) => "STV",
+            MultiWinMethod::PAV(_) => "PAV",
+            MultiWinMethod::SPAV(_) => "SPAV",
+            MultiWinMethod::Phragmen(_) => "Phragmen",
+            MultiWinMethod::MES(_) => "MES",
+            MultiWinMethod::AllocatedScore(_) => "AllocatedScore",
+            MultiWinMethod::SSS(_) => "SSS",
         }
+        .to_string()
     }
 }
 
@@ -89,4 +176,9 @@ pub trait MWMethodSim {
         honest_rslt: Option<WinnerAndRunnerup>,
         nwinners: usize,
     ) -> &Vec<ElectResult>;
+    /// Iterative methods report the cycles their last election ran, and
+    /// whether it stopped on a repeating set of winners.
+    fn iteration_stats(&self) -> Option<(usize, bool)> {
+        None
+    }
 }
@@ -36,14 +36,12 @@ impl Multivote {
 impl MethodSim for MultivoteSim {
     fn elect(&mut self, sim: &Sim, _honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
         self.tallies.fill(0);
-        if let Strategy::Strategic = self.p.strat {
-            for ranks in sim.ranks.axis_iter(Axis(0)) {
-                self.tallies[ranks[0]] += self.p.votes;
-            }
-            return tally_votes(&self.tallies);
-        }
-
         for (icit, utilities) in sim.scores.axis_iter(Axis(0)).enumerate() {
+            if sim.is_strategic(icit, self.p.strat) {
+                // Strategic voters give all their votes to their favorite.
+                self.tallies[sim.ranks[(icit, 0)]] += self.p.votes;
+                continue;
+            }
             let min_score = utilities.iter().map(|x| *x).reduce(f64::min).unwrap();
             let ttl_score: f64 = utilities.iter().map(|x| *x).sum();
             self.cand_scores
@@ -73,23 +71,24 @@ impl MethodSim for MultivoteSim {
         }
 
         log::debug!("Multivote tallies are: {:?}", self.tallies);
-        tally_votes(&self.tallies)
+        tally_votes(&self.tallies, sim)
     }
 
     fn name(&self) -> String {
-        format!("Multivote, {:?}, {} votes", self.p.strat, self.p.votes)
+        format!("Multivote, {}, {} votes", self.p.strat, self.p.votes)
     }
 
     fn colname(&self) -> String {
-        match self.p.strat {
-            Strategy::Honest => format!("multi_h_{}v", self.p.votes),
-            Strategy::Strategic => format!("multi_s_{}v", self.p.votes),
-        }
+        format!("multi_{}_{}v", self.p.strat.as_letter(), self.p.votes)
     }
 
     fn strat(&self) -> Strategy {
         self.p.strat
     }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!("votes: {:?}", self.tallies)]
+    }
 }
 
 #[cfg(test)]
@@ -128,7 +127,7 @@ mod tests {
         assert_eq!(honest_results.runnerup.score, 5.);
 
         let mut smethod = Multivote {
-            strat: Strategy::Strategic,
+            strat: Strategy::Strategic { fraction: 1.0 },
             votes: 4,
             spread_fact: 1.1,
         }
@@ -0,0 +1,298 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::rangevoting::fill_range_ballots;
+use super::results::WinnerAndRunnerup;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// Proportional Approval Voting elects the committee that maximizes the sum,
+/// over voters, of 1 + 1/2 + ... + 1/n, where n is the number of winners the
+/// voter approves. Voters approve the candidates in the upper half of their
+/// range of scores, as in Approval voting (Range with nranks = 2).
+///
+/// When there are at most `max_committees` possible committees, every one is
+/// scored. Otherwise Sequential PAV picks a committee, and winners are swapped
+/// for losers while that raises its score. This usually finds the best committee,
+/// but isn't guaranteed to.
+///
+/// Winners are listed in the order Sequential PAV would elect them from the
+/// committee, with the score each added.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PAV {
+    #[serde(default = "default_max_committees")]
+    pub max_committees: usize,
+}
+
+fn default_max_committees() -> usize {
+    10000
+}
+
+/// Sequential Proportional Approval Voting elects one winner at a time. Each
+/// voter's approvals count 1 / (1 + n), where n is the number of winners so far
+/// that the voter approves, and the candidate with the most weighted approvals
+/// wins the next seat. This is Reweighted Range Voting on approval ballots.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SPAV {}
+
+pub struct PAVSim {
+    p: PAV,
+    approvals: Array2<i32>, // (cit, cand), 1 if approved
+    winners: Vec<ElectResult>,
+}
+
+pub struct SPAVSim {
+    approvals: Array2<i32>,
+    winners: Vec<ElectResult>,
+}
+
+impl PAV {
+    pub fn new_sim(&self, sim: &Sim) -> PAVSim {
+        PAVSim {
+            p: self.clone(),
+            approvals: Array2::zeros((sim.ncit, sim.ncand)),
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl SPAV {
+    pub fn new_sim(&self, sim: &Sim) -> SPAVSim {
+        SPAVSim {
+            approvals: Array2::zeros((sim.ncit, sim.ncand)),
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl MWMethodSim for PAVSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, 2, &mut self.approvals);
+        let nwinners = nwinners.min(sim.ncand);
+        let committee = if sim.ncand <= 64
+            && num_committees(sim.ncand, nwinners) <= self.p.max_committees as f64
+        {
+            best_committee(&self.approvals, nwinners)
+        } else {
+            let all: Vec<usize> = (0..sim.ncand).collect();
+            let mut committee: Vec<usize> = sequential_pav(&self.approvals, &all, nwinners)
+                .iter()
+                .map(|w| w.cand)
+                .collect();
+            improve_by_swaps(&self.approvals, &mut committee);
+            committee
+        };
+        log::debug!("PAV committee: {:?}", committee);
+        self.winners = sequential_pav(&self.approvals, &committee, nwinners);
+        &self.winners
+    }
+}
+
+impl MWMethodSim for SPAVSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, 2, &mut self.approvals);
+        let all: Vec<usize> = (0..sim.ncand).collect();
+        self.winners = sequential_pav(&self.approvals, &all, nwinners);
+        &self.winners
+    }
+}
+
+/// Elects up to nwinners from candidates one at a time, by weighted approvals.
+fn sequential_pav(
+    approvals: &Array2<i32>,
+    candidates: &[usize],
+    nwinners: usize,
+) -> Vec<ElectResult> {
+    let ncit = approvals.nrows();
+    let mut napproved = vec![0; ncit]; // Winners each voter approves
+    let mut remaining = candidates.to_vec();
+    let mut winners = Vec::with_capacity(nwinners);
+    while winners.len() < nwinners && !remaining.is_empty() {
+        let mut best = 0;
+        let mut best_score = f64::MIN;
+        for (idx, &icand) in remaining.iter().enumerate() {
+            let score: f64 = (0..ncit)
+                .filter(|&icit| approvals[(icit, icand)] > 0)
+                .map(|icit| 1.0 / (1 + napproved[icit]) as f64)
+                .sum();
+            if score > best_score {
+                best = idx;
+                best_score = score;
+            }
+        }
+        let winner = remaining.remove(best);
+        for (icit, n) in napproved.iter_mut().enumerate() {
+            if approvals[(icit, winner)] > 0 {
+                *n += 1;
+            }
+        }
+        winners.push(ElectResult {
+            cand: winner,
+            score: best_score,
+        });
+    }
+    winners
+}
+
+/// The PAV score of a committee.
+fn pav_score(approvals: &Array2<i32>, committee: &[usize]) -> f64 {
+    approvals
+        .rows()
+        .into_iter()
+        .map(|ballot| harmonic(committee.iter().filter(|&&c| ballot[c] > 0).count()))
+        .sum()
+}
+
+/// 1 + 1/2 + ... + 1/n
+fn harmonic(n: usize) -> f64 {
+    (1..=n).map(|i| 1.0 / i as f64).sum()
+}
+
+/// The number of ways to choose k of n, as a float so it can't overflow.
+fn num_committees(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |c, i| c * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Scores every committee of nwinners candidates, and returns the best. Voters
+/// with the same approvals are scored together, as bit masks.
+fn best_committee(approvals: &Array2<i32>, nwinners: usize) -> Vec<usize> {
+    let ncand = approvals.ncols();
+    let mut ballots: Vec<(u64, f64)> = Vec::new();
+    for ballot in approvals.rows() {
+        let mask = (0..ncand)
+            .filter(|&c| ballot[c] > 0)
+            .fold(0u64, |m, c| m | (1 << c));
+        match ballots.iter_mut().find(|(m, _)| *m == mask) {
+            Some((_, count)) => *count += 1.0,
+            None => ballots.push((mask, 1.0)),
+        }
+    }
+    let harmonics: Vec<f64> = (0..=nwinners).map(harmonic).collect();
+
+    // Step through committees in lexicographic order of their members.
+    let mut members: Vec<usize> = (0..nwinners).collect();
+    let mut best = members.clone();
+    let mut best_score = f64::MIN;
+    loop {
+        let committee = members.iter().fold(0u64, |m, &c| m | (1 << c));
+        let score: f64 = ballots
+            .iter()
+            .map(|(mask, count)| count * harmonics[(mask & committee).count_ones() as usize])
+            .sum();
+        if score > best_score {
+            best_score = score;
+            best.clone_from(&members);
+        }
+        let Some(i) = (0..nwinners)
+            .rev()
+            .find(|&i| members[i] < ncand - nwinners + i)
+        else {
+            return best;
+        };
+        members[i] += 1;
+        for j in (i + 1)..nwinners {
+            members[j] = members[j - 1] + 1;
+        }
+    }
+}
+
+/// Swaps a winner for a loser while any swap raises the committee's PAV score.
+fn improve_by_swaps(approvals: &Array2<i32>, committee: &mut [usize]) {
+    let ncand = approvals.ncols();
+    let mut score = pav_score(approvals, committee);
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for iseat in 0..committee.len() {
+            for icand in 0..ncand {
+                if committee.contains(&icand) {
+                    continue;
+                }
+                let outgoing = committee[iseat];
+                committee[iseat] = icand;
+                let new_score = pav_score(approvals, committee);
+                if new_score > score + 1.0e-9 {
+                    score = new_score;
+                    improved = true;
+                } else {
+                    committee[iseat] = outgoing;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+
+    #[test]
+    fn test_pav() {
+        // Candidates 0-2 are one party's, and 3-4 another's. A third of the voters
+        // like the first party, and a few approve candidate 5 too.
+        let sim = sim_from_scores(&[
+            (&[1., 1., 1., 0., 0., 0.], 4),
+            (&[1., 1., 1., 0., 0., 1.], 2),
+            (&[0., 0., 0., 1., 1., 0.], 12),
+        ]);
+        // Sequential PAV: 3 has 12 approvals, then 4 has 12 / 2 = 6 against 0's 6,
+        // but 0 comes first, then 4 has 6 against 1's 6 / 2 = 3.
+        let mut spav = SPAV {}.new_sim(&sim);
+        let results = spav.multi_elect(&sim, None, 3);
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        assert_eq!(winners, vec![3, 0, 4]);
+        assert_eq!(results[2].score, 6.0);
+
+        // PAV agrees, whether exact or by swapping.
+        let mut pav = PAV {
+            max_committees: 100,
+        }
+        .new_sim(&sim);
+        let winners: Vec<usize> = pav
+            .multi_elect(&sim, None, 3)
+            .iter()
+            .map(|r| r.cand)
+            .collect();
+        assert_eq!(winners, vec![3, 0, 4]);
+        let mut pav = PAV { max_committees: 0 }.new_sim(&sim);
+        assert_eq!(pav.multi_elect(&sim, None, 3).len(), 3);
+        assert_eq!(num_committees(6, 3), 20.0);
+    }
+
+    #[test]
+    fn test_pav_beats_sequential() {
+        // Everyone in the first two groups approves candidate 0, so Sequential
+        // PAV elects them first, then 1. But 1 and 2 together represent more voters.
+        let sim = sim_from_scores(&[
+            (&[1., 1., 0.], 4),
+            (&[1., 0., 1.], 4),
+            (&[0., 1., 0.], 3),
+            (&[0., 0., 1.], 3),
+        ]);
+        let mut approvals = Array2::zeros((14, 3));
+        fill_range_ballots(&sim, 2, &mut approvals);
+        let spav = sequential_pav(&approvals, &[0, 1, 2], 2);
+        let mut committee: Vec<usize> = spav.iter().map(|w| w.cand).collect();
+        assert_eq!(committee, vec![0, 1]);
+        assert_eq!(pav_score(&approvals, &committee), 4.0 * 1.5 + 4.0 + 3.0);
+        assert_eq!(best_committee(&approvals, 2), vec![1, 2]);
+        improve_by_swaps(&approvals, &mut committee);
+        assert_eq!(committee, vec![2, 1]);
+        assert_eq!(pav_score(&approvals, &committee), 14.0);
+    }
+}
@@ -0,0 +1,108 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::rangevoting::fill_range_ballots;
+use super::results::WinnerAndRunnerup;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// Sequential Phragmén elects one winner at a time on approval ballots, as in
+/// Approval voting (Range with nranks = 2). Each seat is a unit of load shared
+/// by the winner's approvers. The next winner is the candidate whose approvers
+/// would end up with the smallest maximum load, if they spread the new unit so
+/// as to all carry the same. Those approvers then carry that load.
+///
+/// Each winner's score is that load, so lower scores were elected more easily.
+/// A candidate with no approvers has infinite load.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Phragmen {}
+
+pub struct PhragmenSim {
+    approvals: Array2<i32>, // (cit, cand), 1 if approved
+    loads: Vec<f64>,        // Per voter
+    winners: Vec<ElectResult>,
+}
+
+impl Phragmen {
+    pub fn new_sim(&self, sim: &Sim) -> PhragmenSim {
+        PhragmenSim {
+            approvals: Array2::zeros((sim.ncit, sim.ncand)),
+            loads: vec![0.0; sim.ncit],
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl MWMethodSim for PhragmenSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, 2, &mut self.approvals);
+        self.loads.fill(0.0);
+        self.winners.clear();
+        let mut remaining: Vec<usize> = (0..sim.ncand).collect();
+        while self.winners.len() < nwinners && !remaining.is_empty() {
+            let mut best = 0;
+            let mut best_load = f64::INFINITY;
+            for (idx, &icand) in remaining.iter().enumerate() {
+                let mut napprovers = 0;
+                let mut total = 1.0;
+                for icit in 0..sim.ncit {
+                    if self.approvals[(icit, icand)] > 0 {
+                        napprovers += 1;
+                        total += self.loads[icit];
+                    }
+                }
+                let load = if napprovers > 0 {
+                    total / napprovers as f64
+                } else {
+                    f64::INFINITY
+                };
+                if load < best_load || idx == 0 {
+                    best = idx;
+                    best_load = load;
+                }
+            }
+            let winner = remaining.remove(best);
+            for (icit, load) in self.loads.iter_mut().enumerate() {
+                if self.approvals[(icit, winner)] > 0 {
+                    *load = best_load;
+                }
+            }
+            self.winners.push(ElectResult {
+                cand: winner,
+                score: best_load,
+            });
+        }
+        &self.winners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_phragmen() {
+        // Five voters approve candidates 0-2, and three approve candidate 3 only.
+        let sim = sim_from_scores(&[(&[1., 1., 1., 0.], 5), (&[0., 0., 0., 1.], 3)]);
+        let mut phragmen = Phragmen {}.new_sim(&sim);
+        let results = phragmen.multi_elect(&sim, None, 3);
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        // Candidate 0 costs the majority 1/5 each. A second of theirs would
+        // bring them to 2/5, while candidate 3 costs the minority only 1/3.
+        assert_eq!(winners, vec![0, 3, 1]);
+        assert_float_eq!(results[0].score, 0.2, abs <= 1.0e-12);
+        assert_float_eq!(results[1].score, 1.0 / 3.0, abs <= 1.0e-12);
+        assert_float_eq!(results[2].score, 0.4, abs <= 1.0e-12);
+    }
+}
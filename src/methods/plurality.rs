@@ -3,7 +3,7 @@
 
 use super::results::{Strategy, WinnerAndRunnerup};
 use super::tallies::{tally_votes, Tallies};
-use super::MethodSim;
+use super::{Method, MethodSim};
 use crate::sim::Sim;
 use serde::{Deserialize, Serialize};
 
@@ -36,7 +36,8 @@ use serde::{Deserialize, Serialize};
 pub struct Plurality {
     /// Honest voters will vote for the candidate with the highest score, or
     /// perceived utility. Strategic voters will instead limit their choice to
-    /// one of the two front-runners in a pre-election poll.
+    /// one of the two front-runners in a pre-election poll. With a strategic
+    /// fraction below one, the remaining voters vote honestly.
     pub strat: Strategy,
 }
 
@@ -57,24 +58,24 @@ impl Plurality {
 
 impl MethodSim for PluralitySim {
     fn elect(&mut self, sim: &Sim, honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
-        match self.params.strat {
-            Strategy::Honest => {
-                self.tallies.fill(0);
-                for icit in 0..sim.ncit {
-                    self.tallies[sim.ranks[(icit, 0)]] += 1;
-                }
-            }
-            Strategy::Strategic => {
-                let pre_poll = if let Some(prev) = honest_rslt {
-                    prev
+        let pre_poll = match self.params.strat {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => {
+                if let Some(prev) = honest_rslt {
+                    Some(prev)
                 } else {
+                    let strat = self.params.strat;
                     self.params.strat = Strategy::Honest;
                     let prev = self.elect(&sim, None);
-                    self.params.strat = Strategy::Strategic;
-                    prev
-                };
-                self.tallies.fill(0);
-                for icit in 0..sim.ncit {
+                    self.params.strat = strat;
+                    Some(prev)
+                }
+            }
+        };
+        self.tallies.fill(0);
+        for icit in 0..sim.ncit {
+            match pre_poll {
+                Some(pre_poll) if sim.is_strategic(icit, self.params.strat) => {
                     for rank in 0..sim.ncand {
                         let icand = sim.ranks[(icit, rank)];
                         if icand == pre_poll.winner.cand || icand == pre_poll.runnerup.cand {
@@ -83,28 +84,41 @@ impl MethodSim for PluralitySim {
                         }
                     }
                 }
+                _ => {
+                    self.tallies[sim.ranks[(icit, 0)]] += 1;
+                }
             }
         }
         log::debug!(
-            "Plurality votes ({:?}): {:?}",
+            "Plurality votes ({}): {:?}",
             self.params.strat,
             self.tallies
         );
-        tally_votes(&self.tallies)
+        tally_votes(&self.tallies, sim)
     }
 
     fn name(&self) -> String {
-        format!("Plurality, {:?}", self.params.strat)
+        format!("Plurality, {}", self.params.strat)
     }
 
     fn colname(&self) -> String {
-        match self.params.strat {
-            Strategy::Honest => format!("pl_h"),
-            Strategy::Strategic => format!("pl_s"),
-        }
+        format!("pl_{}", self.params.strat.as_letter())
     }
 
     fn strat(&self) -> Strategy {
         self.params.strat
     }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!("votes: {:?}", self.tallies)]
+    }
+
+    fn strategic_prereq(&self) -> Option<Method> {
+        match self.params.strat {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => Some(Method::Plurality(Plurality {
+                strat: Strategy::Honest,
+            })),
+        }
+    }
 }
@@ -1,12 +1,12 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
-use ndarray::{ArrayView, Ix1};
+use ndarray::{Array2, ArrayView, Axis, Ix1};
 use serde::{Deserialize, Serialize};
 
 use super::results::{Strategy, WinnerAndRunnerup};
 use super::tallies::{tally_votes, Tallies};
-use super::MethodSim;
+use super::{Method, MethodSim};
 use crate::sim::Sim;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -41,59 +41,66 @@ impl RangeVoting {
 impl MethodSim for RangeVotingSim {
     fn elect(&mut self, sim: &Sim, honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
         self.tallies.fill(0);
-        // for icit in 0..sim.ncit {
-        for vscores in sim.scores.outer_iter() {
-            match self.params.strat {
-                Strategy::Honest => {
-                    fill_range_ballot(&vscores, self.params.nranks, &mut self.ballot);
-                }
-                Strategy::Strategic => {
-                    let pre_election = honest_rslt.unwrap();
-                    let score_break = (vscores[pre_election.winner.cand]
-                        + vscores[pre_election.runnerup.cand])
-                        / 2.0;
-                    fill_range_ballot_strat(
-                        &vscores,
-                        self.params.nranks,
-                        &mut self.ballot,
-                        score_break,
-                        self.params.strategic_stretch_factor,
-                    );
-                }
+        for (icit, vscores) in sim.scores.outer_iter().enumerate() {
+            if sim.is_strategic(icit, self.params.strat) {
+                let pre_election = honest_rslt.unwrap();
+                let score_break =
+                    (vscores[pre_election.winner.cand] + vscores[pre_election.runnerup.cand]) / 2.0;
+                fill_range_ballot_strat(
+                    &vscores,
+                    self.params.nranks,
+                    &mut self.ballot,
+                    score_break,
+                    self.params.strategic_stretch_factor,
+                );
+            } else {
+                fill_range_ballot(&vscores, self.params.nranks, &mut self.ballot);
             }
             for icand in 0..vscores.len() {
                 self.tallies[icand] += self.ballot[icand];
             }
         }
         log::debug!("{} tallies: {:?}", self.name(), self.tallies);
-        tally_votes(&self.tallies)
+        tally_votes(&self.tallies, sim)
     }
 
     fn name(&self) -> String {
         if self.params.nranks == 2 {
-            format!("Approval, {:?}", self.params.strat)
+            format!("Approval, {}", self.params.strat)
         } else {
-            format!("Range 1-{}, {:?}", self.params.nranks, self.params.strat)
+            format!("Range 1-{}, {}", self.params.nranks, self.params.strat)
         }
     }
 
     fn colname(&self) -> String {
         if self.params.nranks == 2 {
-            match self.params.strat {
-                Strategy::Honest => format!("aprv_h"),
-                Strategy::Strategic => format!("aprv_s"),
-            }
+            format!("aprv_{}", self.params.strat.as_letter())
         } else {
-            match self.params.strat {
-                Strategy::Honest => format!("range_{}_h", self.params.nranks),
-                Strategy::Strategic => format!("range_{}_s", self.params.nranks),
-            }
+            format!(
+                "range_{}_{}",
+                self.params.nranks,
+                self.params.strat.as_letter()
+            )
         }
     }
 
     fn strat(&self) -> Strategy {
         self.params.strat
     }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        vec![format!("score totals: {:?}", self.tallies)]
+    }
+
+    fn strategic_prereq(&self) -> Option<Method> {
+        match self.params.strat {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => Some(Method::Range(RangeVoting {
+                strat: Strategy::Honest,
+                ..self.params.clone()
+            })),
+        }
+    }
 }
 
 pub fn fill_range_ballot(scores: &ArrayView<f64, Ix1>, ranks: i32, ballot: &mut [i32]) {
@@ -109,6 +116,21 @@ pub fn fill_range_ballot(scores: &ArrayView<f64, Ix1>, ranks: i32, ballot: &mut
     }
 }
 
+/// Fills ballots (cit, cand) with every voter's honest range ballot. With
+/// two ranks, these are approval ballots of 0s and 1s.
+pub fn fill_range_ballots(sim: &Sim, ranks: i32, ballots: &mut Array2<i32>) {
+    for icit in 0..sim.ncit {
+        fill_range_ballot(
+            &sim.scores.index_axis(Axis(0), icit),
+            ranks,
+            ballots
+                .index_axis_mut(Axis(0), icit)
+                .as_slice_mut()
+                .unwrap(),
+        );
+    }
+}
+
 pub fn fill_range_ballot_strat(
     scores: &ArrayView<f64, Ix1>,
     ranks: i32,
@@ -176,12 +198,24 @@ mod tests {
         assert_eq!(honest_results.runnerup.cand, 0);
 
         let mut method2 = RangeVoting {
-            strat: Strategy::Strategic,
+            strat: Strategy::Strategic { fraction: 1.0 },
             nranks: 10,
             strategic_stretch_factor: 1000., // votes become 0's and 10's
         }
         .new_sim(&sim);
         let strat_results = method2.elect(&sim, Some(honest_results));
         assert_eq!(strat_results.winner.cand, 1);
+
+        // Only the first three voters are strategic
+        let mut method3 = RangeVoting {
+            strat: Strategy::Strategic { fraction: 0.5 },
+            nranks: 10,
+            strategic_stretch_factor: 1000.,
+        }
+        .new_sim(&sim);
+        let mixed_results = method3.elect(&sim, Some(honest_results));
+        assert_eq!(method3.tallies, vec![27, 28, 29]);
+        assert_eq!(mixed_results.winner.cand, 2);
+        assert_eq!(method3.colname(), "range_10_s50");
     }
 }
@@ -59,19 +59,27 @@ impl MethodSim for RPSim {
     }
 
     fn name(&self) -> String {
-        format!("RP, {:?}", self.params.strat)
+        format!("RP, {}", self.params.strat)
     }
 
     fn colname(&self) -> String {
-        match self.params.strat {
-            Strategy::Honest => "rp_h".to_string(),
-            Strategy::Strategic => "rp_s".to_string(),
-        }
+        format!("rp_{}", self.params.strat.as_letter())
     }
 
     fn strat(&self) -> Strategy {
         self.params.strat
     }
+
+    fn explain(&self, sim: &Sim) -> Vec<String> {
+        let mut pairs = Vec::new();
+        find_candidate_pairoffs(&mut pairs, sim);
+        pairs.sort_by_key(|p| -p.margin);
+        let pairs: Vec<String> = pairs
+            .iter()
+            .map(|p| format!("{}>{} by {}", p.winner, p.loser, p.margin))
+            .collect();
+        vec![format!("pairs in lock-in order: {}", pairs.join(", "))]
+    }
 }
 
 impl RPSim {
@@ -2,8 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
-// use std::string::ToString;
-use strum_macros::Display;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ElectResult {
@@ -18,12 +17,70 @@ pub struct WinnerAndRunnerup {
     pub runnerup: ElectResult,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Display)]
+/// In config files, `strat = "Strategic"` makes every voter strategic, and
+/// `strat = { Strategic = { fraction = 0.3 } }` makes 30% of voters strategic
+/// while the rest vote honestly.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StrategySpec", into = "StrategySpec")]
 pub enum Strategy {
+    Honest,
+    Strategic { fraction: f64 },
+}
+
+/// The forms a Strategy can take in a config file.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum StrategySpec {
+    Plain(PlainStrategy),
+    Partial(PartialStrategy),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum PlainStrategy {
     Honest,
     Strategic,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+enum PartialStrategy {
+    Strategic { fraction: f64 },
+}
+
+impl TryFrom<StrategySpec> for Strategy {
+    type Error = String;
+
+    fn try_from(spec: StrategySpec) -> Result<Self, Self::Error> {
+        match spec {
+            StrategySpec::Plain(PlainStrategy::Honest) => Ok(Strategy::Honest),
+            StrategySpec::Plain(PlainStrategy::Strategic) => {
+                Ok(Strategy::Strategic { fraction: 1.0 })
+            }
+            StrategySpec::Partial(PartialStrategy::Strategic { fraction }) => {
+                if !(0.0..=1.0).contains(&fraction) {
+                    Err(format!(
+                        "strategic fraction must be between 0 and 1, got {}",
+                        fraction
+                    ))
+                } else {
+                    Ok(Strategy::Strategic { fraction })
+                }
+            }
+        }
+    }
+}
+
+impl From<Strategy> for StrategySpec {
+    fn from(strat: Strategy) -> Self {
+        match strat {
+            Strategy::Honest => StrategySpec::Plain(PlainStrategy::Honest),
+            Strategy::Strategic { fraction: 1.0 } => StrategySpec::Plain(PlainStrategy::Strategic),
+            Strategy::Strategic { fraction } => {
+                StrategySpec::Partial(PartialStrategy::Strategic { fraction })
+            }
+        }
+    }
+}
+
 pub fn default_honest() -> Strategy {
     Strategy::Honest
 }
@@ -34,11 +91,60 @@ impl WinnerAndRunnerup {
     }
 }
 
+/// The percent of strategic voters, rounded just enough to hide float noise
+/// (0.07 * 100.0 is 7.000000000000001) so it prints as "7" or "12.5".
+fn percent(fraction: f64) -> f64 {
+    (fraction * 100.0 * 1.0e9).round() / 1.0e9
+}
+
 impl Strategy {
-    pub fn as_letter(&self) -> &str {
+    /// A short tag for column names: "h", "s", or "s30" when 30% of voters are strategic.
+    pub fn as_letter(&self) -> String {
+        match self {
+            Strategy::Honest => "h".to_string(),
+            Strategy::Strategic { fraction } if *fraction == 1.0 => "s".to_string(),
+            Strategy::Strategic { fraction } => format!("s{}", percent(*fraction)),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Strategy::Honest => "h",
-            Strategy::Strategic => "s",
+            Strategy::Honest => write!(f, "Honest"),
+            Strategy::Strategic { fraction } if *fraction == 1.0 => write!(f, "Strategic"),
+            Strategy::Strategic { fraction } => write!(f, "Strategic {}%", percent(*fraction)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Params {
+        strat: Strategy,
+    }
+
+    #[test]
+    fn test_strategy_config() {
+        let p: Params = toml::from_str(r#"strat = "Honest""#).unwrap();
+        assert_eq!(p.strat, Strategy::Honest);
+        let p: Params = toml::from_str(r#"strat = "Strategic""#).unwrap();
+        assert_eq!(p.strat, Strategy::Strategic { fraction: 1.0 });
+        assert_eq!(p.strat.as_letter(), "s");
+        let p: Params = toml::from_str(r#"strat = { Strategic = { fraction = 0.3 } }"#).unwrap();
+        assert_eq!(p.strat, Strategy::Strategic { fraction: 0.3 });
+        assert_eq!(p.strat.as_letter(), "s30");
+        assert_eq!(p.strat.to_string(), "Strategic 30%");
+        assert!(toml::from_str::<Params>(r#"strat = { Strategic = { fraction = 1.3 } }"#).is_err());
+        let p: Params = toml::from_str(r#"strat = { Strategic = { fraction = 0.07 } }"#).unwrap();
+        assert_eq!(p.strat.as_letter(), "s7");
+        let p: Params = toml::from_str(r#"strat = { Strategic = { fraction = 0.125 } }"#).unwrap();
+        assert_eq!(p.strat.as_letter(), "s12.5");
+        assert_eq!(p.strat.to_string(), "Strategic 12.5%");
+        let p: Params = toml::from_str(r#"strat = { Strategic = { fraction = 0.304 } }"#).unwrap();
+        assert_eq!(p.strat.as_letter(), "s30.4");
+    }
+}
@@ -1,10 +1,10 @@
 // © Copyright 2025 Topher Cawlfield
 // SPDX-License-Identifier: Apache-2.0
 
-use ndarray::{Array2, Axis};
+use ndarray::Array2;
 use serde::{Deserialize, Serialize};
 
-use super::rangevoting::fill_range_ballot;
+use super::rangevoting::fill_range_ballots;
 use super::results::{Strategy, WinnerAndRunnerup};
 use super::MWMethodSim;
 use crate::methods::ElectResult;
@@ -17,12 +17,37 @@ pub struct RRV {
     pub k: f64,
 }
 
+/// Iterative RRV starts from the RRV winners, then cycles through the seats:
+/// each winner in turn is removed, and the seat goes to whichever candidate,
+/// possibly the same one, has the highest score reweighted by the other
+/// winners. This repeats until a whole cycle changes nothing, the set of
+/// winners repeats one seen after an earlier cycle, or max_cycles cycles have
+/// run.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IterativeRRV {
+    pub strat: Strategy,
+    pub ranks: i32,
+    pub k: f64,
+    #[serde(default = "default_max_cycles")]
+    pub max_cycles: usize,
+}
+
+fn default_max_cycles() -> usize {
+    20
+}
+
 pub struct RRVSim {
     p: RRV,
     wtd_scores: Vec<f64>,
     ballots: Array2<i32>,
     winners: Vec<ElectResult>,
     remaining: Vec<usize>,
+    /// Cycles through the winners after electing them, if set, up to this many.
+    max_cycles: Option<usize>,
+    /// Cycles run by the last election
+    iterations: usize,
+    /// Whether the last election stopped on a repeating set of winners
+    cycle_detected: bool,
 }
 
 /*
@@ -38,8 +63,96 @@ impl RRV {
             ballots: Array2::zeros((sim.ncit, sim.ncand)),
             winners: Vec::with_capacity(sim.ncand),
             remaining: Vec::with_capacity(sim.ncand),
+            max_cycles: None,
+            iterations: 0,
+            cycle_detected: false,
+        }
+    }
+}
+
+impl IterativeRRV {
+    pub fn new_sim(&self, sim: &Sim) -> RRVSim {
+        let rrv = RRV {
+            strat: self.strat,
+            ranks: self.ranks,
+            k: self.k,
+        };
+        let mut rrv_sim = rrv.new_sim(sim);
+        rrv_sim.max_cycles = Some(self.max_cycles);
+        rrv_sim
+    }
+}
+
+impl RRVSim {
+    /// Fills wtd_scores for the remaining candidates, with ballots reweighted
+    /// by every winner but the one in seat skip.
+    fn tally(&mut self, ncit: usize, skip: Option<usize>) {
+        self.wtd_scores.fill(0.0);
+        for i in 0..ncit {
+            // Weight is K / (K + SUM/MAX)
+            let sum = self
+                .winners
+                .iter()
+                .enumerate()
+                .filter(|(iseat, _)| Some(*iseat) != skip)
+                .fold(0, |sum, (_, j)| sum + self.ballots[(i, j.cand)]);
+            let wt = self.p.k / (self.p.k + (sum as f64) / ((self.p.ranks - 1) as f64));
+            for j in self.remaining.iter() {
+                self.wtd_scores[*j] += wt * (self.ballots[(i, *j)] as f64);
+            }
+        }
+    }
+
+    /// Removes the remaining candidate with the highest weighted score.
+    fn take_best(&mut self) -> ElectResult {
+        let (winner_idx, winner_score) = {
+            let mut rem_iter = self.remaining.iter();
+            let mut winner_idx = 0;
+            let mut winner_score = self.wtd_scores[*rem_iter.next().unwrap()];
+            for (idx, j) in rem_iter.enumerate() {
+                if self.wtd_scores[*j] > winner_score {
+                    winner_idx = idx + 1;
+                    winner_score = self.wtd_scores[*j];
+                }
+            }
+            (winner_idx, winner_score)
+        };
+        ElectResult {
+            cand: self.remaining.swap_remove(winner_idx),
+            score: winner_score,
+        }
+    }
+
+    /// Re-elects each seat in turn until the winners settle or repeat.
+    fn iterate(&mut self, ncit: usize, max_cycles: usize) {
+        let mut seen: Vec<Vec<usize>> = vec![self.winner_set()];
+        while self.iterations < max_cycles {
+            self.iterations += 1;
+            let mut changed = false;
+            for iseat in 0..self.winners.len() {
+                self.remaining.push(self.winners[iseat].cand);
+                self.tally(ncit, Some(iseat));
+                let replacement = self.take_best();
+                changed |= replacement.cand != self.winners[iseat].cand;
+                self.winners[iseat] = replacement;
+            }
+            if !changed {
+                return;
+            }
+            let winner_set = self.winner_set();
+            if seen.contains(&winner_set) {
+                self.cycle_detected = true;
+                return;
+            }
+            seen.push(winner_set);
         }
     }
+
+    fn winner_set(&self) -> Vec<usize> {
+        let mut cands: Vec<usize> = self.winners.iter().map(|w| w.cand).collect();
+        cands.sort_unstable();
+        cands
+    }
 }
 
 impl MWMethodSim for RRVSim {
@@ -49,54 +162,34 @@ impl MWMethodSim for RRVSim {
         _honest_rslt: Option<WinnerAndRunnerup>,
         nwinners: usize,
     ) -> &Vec<ElectResult> {
-        self.ballots.fill(0);
-        for icit in 0..sim.ncit {
-            fill_range_ballot(
-                &sim.scores.index_axis(Axis(0), icit),
-                self.p.ranks,
-                self.ballots
-                    .index_axis_mut(Axis(0), icit)
-                    .as_slice_mut()
-                    .unwrap(),
-            );
-        }
+        fill_range_ballots(sim, self.p.ranks, &mut self.ballots);
 
         self.remaining.clear();
         self.remaining.extend(0..sim.ncand);
         self.winners.clear();
         while self.winners.len() < nwinners {
-            self.wtd_scores.fill(0.0);
-            for i in 0..sim.ncit {
-                // Weight is K / (K + SUM/MAX)
-                let sum = self
-                    .winners
-                    .iter()
-                    .fold(0, |sum, j| sum + self.ballots[(i, j.cand)]);
-                let wt = self.p.k / (self.p.k + (sum as f64) / ((self.p.ranks - 1) as f64));
-                for j in self.remaining.iter() {
-                    self.wtd_scores[*j] += wt * (self.ballots[(i, *j)] as f64);
-                }
-            }
-            let (winner_idx, winner_score) = {
-                let mut rem_iter = self.remaining.iter();
-                let mut winner_idx = 0;
-                let mut winner_score = self.wtd_scores[*rem_iter.next().unwrap()];
-                for (idx, j) in rem_iter.enumerate() {
-                    if self.wtd_scores[*j] > winner_score {
-                        winner_idx = idx + 1;
-                        winner_score = self.wtd_scores[*j];
-                    }
-                }
-                (winner_idx, winner_score)
-            };
-            let winner = self.remaining.swap_remove(winner_idx);
-            self.winners.push(ElectResult {
-                cand: winner,
-                score: winner_score,
-            });
+            self.tally(sim.ncit, None);
+            let winner = self.take_best();
+            self.winners.push(winner);
+        }
+
+        self.iterations = 0;
+        self.cycle_detected = false;
+        if let Some(max_cycles) = self.max_cycles {
+            self.iterate(sim.ncit, max_cycles);
+            log::debug!(
+                "Iterative RRV ran {} cycles, cycle detected: {}",
+                self.iterations,
+                self.cycle_detected
+            );
         }
         &self.winners
     }
+
+    fn iteration_stats(&self) -> Option<(usize, bool)> {
+        self.max_cycles
+            .map(|_| (self.iterations, self.cycle_detected))
+    }
 }
 
 #[cfg(test)]
@@ -104,6 +197,7 @@ mod tests {
     use float_eq::assert_float_eq;
 
     use super::*;
+    use crate::methods::test_utils::sim_from_scores;
     use crate::methods::ElectResult;
     use crate::sim::Sim;
 
@@ -169,4 +263,62 @@ mod tests {
         assert_eq!(rrv.ballots[(0, 0)], 10);
         assert_eq!(rrv.ballots[(0, 4)], 0);
     }
+
+    #[test]
+    fn test_iterative_rrv() {
+        // Candidate 2 is everyone's compromise, and wins the first seat. But the
+        // factions' favorites, 0 and 1, make a better pair.
+        let sim = sim_from_scores(&[(&[10., 0., 6.], 50), (&[0., 10., 6.], 50)]);
+        let mut rrv = RRV {
+            strat: Strategy::Honest,
+            ranks: 11,
+            k: 1.0,
+        }
+        .new_sim(&sim);
+        let winners: Vec<usize> = rrv
+            .multi_elect(&sim, None, 2)
+            .iter()
+            .map(|r| r.cand)
+            .collect();
+        assert_eq!(winners, vec![2, 0]);
+        assert_eq!(rrv.iterations, 0);
+        assert_eq!(rrv.iteration_stats(), None);
+
+        // Without 2, candidate 0 halves the first faction's weight, so 1 gets 500
+        // points, and 2 only 50 * 6 * 0.5 + 50 * 6. The next cycle changes nothing.
+        let mut rrv = IterativeRRV {
+            strat: Strategy::Honest,
+            ranks: 11,
+            k: 1.0,
+            max_cycles: 10,
+        }
+        .new_sim(&sim);
+        let results = rrv.multi_elect(&sim, None, 2);
+        assert_eq!(
+            results,
+            &vec![
+                ElectResult {
+                    cand: 1,
+                    score: 500.
+                },
+                ElectResult {
+                    cand: 0,
+                    score: 500.
+                },
+            ]
+        );
+        assert_eq!(rrv.iterations, 2);
+        assert!(!rrv.cycle_detected);
+        assert_eq!(rrv.iteration_stats(), Some((2, false)));
+
+        let mut rrv = IterativeRRV {
+            strat: Strategy::Honest,
+            ranks: 11,
+            k: 1.0,
+            max_cycles: 1,
+        }
+        .new_sim(&sim);
+        rrv.multi_elect(&sim, None, 2);
+        assert_eq!(rrv.iterations, 1);
+    }
 }
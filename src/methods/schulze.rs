@@ -0,0 +1,239 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::condorcet_util::count_pairwise_preferences;
+use super::results::{ElectResult, Strategy, WinnerAndRunnerup};
+use super::MethodSim;
+use crate::sim::Sim;
+
+/// The Schulze method, also known as the beatpath method, is a Condorcet method
+/// on ranked ballots. Each pairwise defeat is a link with some strength, and
+/// a path from A to B through other candidates is as strong as its weakest link.
+/// A beats B in the Schulze ordering if the strongest path from A to B is stronger
+/// than the strongest path from B to A. This ordering is always transitive, so there
+/// is always a winner, and the winner is always in the Smith set.
+///
+/// The winner and runner-up are scored by the number of candidates they beat
+/// in the Schulze ordering.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Schulze {
+    /// How the strength of a pairwise defeat is measured. Defaults to WinningVotes.
+    #[serde(default = "default_strength")]
+    pub strength: DefeatStrength,
+}
+
+/// With complete and strict rankings the two measures are equivalent. They
+/// differ when voters score some candidates equally.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum DefeatStrength {
+    /// The number of voters preferring the winner of the pair.
+    WinningVotes,
+    /// Winning votes minus losing votes.
+    Margins,
+}
+
+fn default_strength() -> DefeatStrength {
+    DefeatStrength::WinningVotes
+}
+
+#[derive(Debug)]
+pub struct SchulzeSim {
+    params: Schulze,
+    prefs: Array2<i32>,
+    paths: Array2<i32>, // Strength of the strongest path from i to j
+    path_wins: Vec<i32>,
+    cands: Vec<usize>,
+}
+
+impl Schulze {
+    pub fn new_sim(&self, sim: &Sim) -> SchulzeSim {
+        SchulzeSim {
+            params: self.clone(),
+            prefs: Array2::zeros((sim.ncand, sim.ncand)),
+            paths: Array2::zeros((sim.ncand, sim.ncand)),
+            path_wins: vec![0; sim.ncand],
+            cands: (0..sim.ncand).collect(),
+        }
+    }
+}
+
+impl MethodSim for SchulzeSim {
+    fn elect(&mut self, sim: &Sim, _honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
+        // Direct links. Only defeats are links; losses and ties have strength zero.
+        match self.params.strength {
+            DefeatStrength::WinningVotes => {
+                count_pairwise_preferences(&mut self.prefs, sim);
+                for i in 0..sim.ncand {
+                    for j in 0..sim.ncand {
+                        self.paths[(i, j)] = if self.prefs[(i, j)] > self.prefs[(j, i)] {
+                            self.prefs[(i, j)]
+                        } else {
+                            0
+                        };
+                    }
+                }
+            }
+            DefeatStrength::Margins => {
+                for i in 0..sim.ncand {
+                    for j in 0..sim.ncand {
+                        self.paths[(i, j)] = sim.i_beats_j_by[(i, j)].max(0);
+                    }
+                }
+            }
+        }
+
+        // Widest paths, Floyd-Warshall style.
+        for k in 0..sim.ncand {
+            for i in 0..sim.ncand {
+                if i == k {
+                    continue;
+                }
+                for j in 0..sim.ncand {
+                    if j == i || j == k {
+                        continue;
+                    }
+                    let through_k = self.paths[(i, k)].min(self.paths[(k, j)]);
+                    if through_k > self.paths[(i, j)] {
+                        self.paths[(i, j)] = through_k;
+                    }
+                }
+            }
+        }
+        log::debug!("Schulze strongest paths: {:?}", self.paths);
+
+        // The Schulze ordering is transitive, so anyone who beats a candidate
+        // also beats everyone that candidate beats. Counting wins ranks them.
+        for (i, wins) in self.path_wins.iter_mut().enumerate() {
+            *wins = (0..sim.ncand)
+                .filter(|&j| self.paths[(i, j)] > self.paths[(j, i)])
+                .count() as i32;
+        }
+        self.cands.clear();
+        self.cands.extend(0..sim.ncand);
+        self.cands.sort_by_key(|&icand| -self.path_wins[icand]);
+        let (winner, runner_up) = (self.cands[0], self.cands[1]);
+        WinnerAndRunnerup {
+            winner: ElectResult {
+                cand: winner,
+                score: self.path_wins[winner] as f64,
+            },
+            runnerup: ElectResult {
+                cand: runner_up,
+                score: self.path_wins[runner_up] as f64,
+            },
+        }
+    }
+
+    fn name(&self) -> String {
+        match self.params.strength {
+            DefeatStrength::WinningVotes => "Schulze (winning votes), Honest".to_string(),
+            DefeatStrength::Margins => "Schulze (margins), Honest".to_string(),
+        }
+    }
+
+    fn colname(&self) -> String {
+        match self.params.strength {
+            DefeatStrength::WinningVotes => "schulze_wv_h".to_string(),
+            DefeatStrength::Margins => "schulze_mg_h".to_string(),
+        }
+    }
+
+    fn strat(&self) -> Strategy {
+        Strategy::Honest
+    }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        let mut lines = vec!["strongest paths:".to_string()];
+        for row in self.paths.outer_iter() {
+            lines.push(format!("  {}", row));
+        }
+        lines.push(format!("candidates beaten: {:?}", self.path_wins));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+    use ndarray::array;
+
+    #[test]
+    fn test_schulze() {
+        // Example from https://electowiki.org/wiki/Schulze_method
+        // Candidates are A, B, C, D, E. Ballot scores are minus the rank.
+        let mut sim = sim_from_scores(&[
+            (&[-1., -3., -2., -5., -4.], 5), // A>C>B>E>D
+            (&[-1., -5., -4., -2., -3.], 5), // A>D>E>C>B
+            (&[-4., -1., -5., -3., -2.], 8), // B>E>D>A>C
+            (&[-2., -3., -1., -5., -4.], 3), // C>A>B>E>D
+            (&[-2., -4., -1., -5., -3.], 7), // C>A>E>B>D
+            (&[-3., -2., -1., -4., -5.], 2), // C>B>A>D>E
+            (&[-5., -4., -2., -1., -3.], 7), // D>C>E>B>A
+            (&[-3., -2., -5., -4., -1.], 8), // E>B>A>D>C
+        ]);
+        sim.rank_candidates();
+        let mut method = Schulze {
+            strength: DefeatStrength::WinningVotes,
+        }
+        .new_sim(&sim);
+        let results = method.elect(&sim, None);
+        #[rustfmt::skip]
+        assert_eq!(method.paths, array![
+            [ 0, 28, 28, 30, 24],
+            [25,  0, 28, 33, 24],
+            [25, 29,  0, 29, 24],
+            [25, 28, 28,  0, 24],
+            [25, 28, 28, 31,  0],
+        ]);
+        // Schulze ordering is E > A > C > B > D
+        assert_eq!(method.path_wins, vec![3, 1, 2, 0, 4]);
+        assert_eq!(results.winner.cand, 4);
+        assert_eq!(results.winner.score, 4.);
+        assert_eq!(results.runnerup.cand, 0);
+        assert_eq!(results.runnerup.score, 3.);
+
+        // Strict rankings, so margins must agree.
+        let mut method = Schulze {
+            strength: DefeatStrength::Margins,
+        }
+        .new_sim(&sim);
+        let results = method.elect(&sim, None);
+        assert_eq!(method.path_wins, vec![3, 1, 2, 0, 4]);
+        assert_eq!(results.winner.cand, 4);
+        assert_eq!(results.runnerup.cand, 0);
+    }
+
+    #[test]
+    fn test_schulze_wv_vs_margins() {
+        // Equal scores make the two defeat strengths disagree.
+        // Pairwise: B>A 4-2, A>C 5-4, C>B 6-3 -- a cycle.
+        let mut sim = sim_from_scores(&[
+            (&[2., 2., 0.], 3), // A=B>C
+            (&[0., 1., 2.], 4), // C>B>A
+            (&[2., 0., 1.], 2), // A>C>B
+        ]);
+        sim.rank_candidates();
+
+        // Winning votes: B>A (4) is the weakest defeat, leaving A > C > B.
+        let mut method = Schulze {
+            strength: DefeatStrength::WinningVotes,
+        }
+        .new_sim(&sim);
+        let results = method.elect(&sim, None);
+        assert_eq!(results.winner.cand, 0);
+        assert_eq!(results.runnerup.cand, 2);
+
+        // Margins: A>C (by 1) is the weakest defeat, leaving C > B > A.
+        let mut method = Schulze {
+            strength: DefeatStrength::Margins,
+        }
+        .new_sim(&sim);
+        let results = method.elect(&sim, None);
+        assert_eq!(results.winner.cand, 2);
+        assert_eq!(results.runnerup.cand, 1);
+    }
+}
@@ -0,0 +1,123 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use super::allocated_score::default_quota;
+use super::rangevoting::fill_range_ballots;
+use super::results::WinnerAndRunnerup;
+use super::stv::Quota;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// Sequentially Spent Score gives every voter a budget of one maximum score.
+/// Each voter's scores count for no more than what is left of their budget, and
+/// the candidate with the highest total wins. The winner's supporters then
+/// spend what they gave, scaled down so that all together they spend one
+/// quota, or spend all they gave if that falls short.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SSS {
+    pub ranks: i32,
+    /// Defaults to Hare.
+    #[serde(default = "default_quota")]
+    pub quota: Quota,
+}
+
+pub struct SSSSim {
+    p: SSS,
+    ballots: Array2<i32>, // (cit, cand)
+    budgets: Vec<f64>,    // In maximum scores
+    winners: Vec<ElectResult>,
+}
+
+impl SSS {
+    pub fn new_sim(&self, sim: &Sim) -> SSSSim {
+        SSSSim {
+            p: self.clone(),
+            ballots: Array2::zeros((sim.ncit, sim.ncand)),
+            budgets: vec![1.0; sim.ncit],
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl SSSSim {
+    /// What a voter's score for a candidate is worth now, in maximum scores.
+    fn spendable(&self, icit: usize, icand: usize) -> f64 {
+        let max_score = (self.p.ranks - 1) as f64;
+        (self.ballots[(icit, icand)] as f64 / max_score).min(self.budgets[icit])
+    }
+}
+
+impl MWMethodSim for SSSSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        fill_range_ballots(sim, self.p.ranks, &mut self.ballots);
+        self.budgets.fill(1.0);
+        self.winners.clear();
+        let quota = self.p.quota.votes(sim.ncit as f64, nwinners);
+        let mut remaining: Vec<usize> = (0..sim.ncand).collect();
+        while self.winners.len() < nwinners && !remaining.is_empty() {
+            let mut best = 0;
+            let mut best_total = f64::MIN;
+            for (idx, &icand) in remaining.iter().enumerate() {
+                let total: f64 = (0..sim.ncit).map(|icit| self.spendable(icit, icand)).sum();
+                if total > best_total {
+                    best = idx;
+                    best_total = total;
+                }
+            }
+            let winner = remaining.remove(best);
+            let spent_frac = if best_total > quota {
+                quota / best_total
+            } else {
+                1.0
+            };
+            for icit in 0..sim.ncit {
+                self.budgets[icit] -= self.spendable(icit, winner) * spent_frac;
+            }
+            self.winners.push(ElectResult {
+                cand: winner,
+                score: best_total * (self.p.ranks - 1) as f64,
+            });
+        }
+        &self.winners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_sss() {
+        let sim = sim_from_scores(&[(&[4., 4., 0.], 7), (&[2., 0., 4.], 3)]);
+        let mut sss = SSS {
+            ranks: 5,
+            quota: Quota::Hare,
+        }
+        .new_sim(&sim);
+        let results = sss.multi_elect(&sim, None, 3);
+        // Candidate 0 has 8.5 maximum scores, and the quota is 10/3, so every
+        // supporter spends 20/51 of what they gave.
+        let winners: Vec<usize> = results.iter().map(|r| r.cand).collect();
+        assert_eq!(winners, vec![0, 1, 2]);
+        assert_float_eq!(results[0].score, 34.0, abs <= 1.0e-12);
+        assert_float_eq!(results[1].score, 4.0 * 7.0 * 31.0 / 51.0, abs <= 1.0e-12);
+
+        // The minority spent a little on 0, and gives all it has left to 2.
+        assert_float_eq!(
+            results[2].score,
+            4.0 * 3.0 * (1.0 - 0.5 * 20.0 / 51.0),
+            abs <= 1.0e-12
+        );
+    }
+}
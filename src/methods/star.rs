@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use super::rangevoting::{fill_range_ballot, fill_range_ballot_strat};
 use super::results::{ElectResult, Strategy, WinnerAndRunnerup};
 use super::tallies::{tally_votes, Tallies};
-use super::MethodSim;
+use super::{Method, MethodSim};
 use crate::sim::Sim;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -33,6 +33,7 @@ pub struct STARSim {
     tallies: Tallies,
     ballot: Tallies,
     preference_matrix: Array2<i32>,
+    finalists: (usize, usize),
 }
 
 impl STAR {
@@ -42,6 +43,7 @@ impl STAR {
             tallies: vec![0; sim.ncand],
             ballot: vec![0; sim.ncand],
             preference_matrix: Array2::zeros((sim.ncand, sim.ncand)),
+            finalists: (0, 1),
         }
     }
 }
@@ -50,25 +52,20 @@ impl MethodSim for STARSim {
     fn elect(&mut self, sim: &Sim, honest_rslt: Option<WinnerAndRunnerup>) -> WinnerAndRunnerup {
         self.tallies.fill(0);
         self.preference_matrix.fill(0);
-        // for icit in 0..sim.ncit {
-        for vscores in sim.scores.outer_iter() {
-            match self.params.strat {
-                Strategy::Honest => {
-                    fill_range_ballot(&vscores, self.params.nranks, &mut self.ballot);
-                }
-                Strategy::Strategic => {
-                    let pre_election = honest_rslt.unwrap();
-                    let score_break = (vscores[pre_election.winner.cand]
-                        + vscores[pre_election.runnerup.cand])
-                        / 2.0;
-                    fill_range_ballot_strat(
-                        &vscores,
-                        self.params.nranks,
-                        &mut self.ballot,
-                        score_break,
-                        self.params.strategic_stretch_factor,
-                    );
-                }
+        for (icit, vscores) in sim.scores.outer_iter().enumerate() {
+            if sim.is_strategic(icit, self.params.strat) {
+                let pre_election = honest_rslt.unwrap();
+                let score_break =
+                    (vscores[pre_election.winner.cand] + vscores[pre_election.runnerup.cand]) / 2.0;
+                fill_range_ballot_strat(
+                    &vscores,
+                    self.params.nranks,
+                    &mut self.ballot,
+                    score_break,
+                    self.params.strategic_stretch_factor,
+                );
+            } else {
+                fill_range_ballot(&vscores, self.params.nranks, &mut self.ballot);
             }
             for icand in 0..vscores.len() {
                 self.tallies[icand] += self.ballot[icand];
@@ -84,9 +81,10 @@ impl MethodSim for STARSim {
             }
         }
         log::debug!("{} tallies: {:?}", self.name(), self.tallies);
-        let runoff = tally_votes(&self.tallies);
+        let runoff = tally_votes(&self.tallies, sim);
         let ca = runoff.winner.cand;
         let cb = runoff.runnerup.cand;
+        self.finalists = (ca, cb);
         if self.preference_matrix[(ca, cb)] >= self.preference_matrix[(cb, ca)] {
             WinnerAndRunnerup {
                 winner: ElectResult {
@@ -113,19 +111,44 @@ impl MethodSim for STARSim {
     }
 
     fn name(&self) -> String {
-        format!("STAR 1-{}, {:?}", self.params.nranks, self.params.strat)
+        format!("STAR 1-{}, {}", self.params.nranks, self.params.strat)
     }
 
     fn colname(&self) -> String {
-        match self.params.strat {
-            Strategy::Honest => format!("star_{}_h", self.params.nranks),
-            Strategy::Strategic => format!("star_{}_s", self.params.nranks),
-        }
+        format!(
+            "star_{}_{}",
+            self.params.nranks,
+            self.params.strat.as_letter()
+        )
     }
 
     fn strat(&self) -> Strategy {
         self.params.strat
     }
+
+    fn explain(&self, _sim: &Sim) -> Vec<String> {
+        let (ca, cb) = self.finalists;
+        vec![
+            format!("score totals: {:?}", self.tallies),
+            format!(
+                "runoff: {} preferred by {}, {} preferred by {}",
+                ca,
+                self.preference_matrix[(ca, cb)],
+                cb,
+                self.preference_matrix[(cb, ca)]
+            ),
+        ]
+    }
+
+    fn strategic_prereq(&self) -> Option<Method> {
+        match self.params.strat {
+            Strategy::Honest => None,
+            Strategy::Strategic { .. } => Some(Method::STAR(STAR {
+                strat: Strategy::Honest,
+                ..self.params.clone()
+            })),
+        }
+    }
 }
 
 #[cfg(test)]
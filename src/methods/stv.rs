@@ -0,0 +1,198 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Axis;
+use serde::{Deserialize, Serialize};
+
+use super::results::WinnerAndRunnerup;
+use super::MWMethodSim;
+use crate::methods::ElectResult;
+use crate::sim::Sim;
+
+/// Single Transferable Vote is a proportional multi-winner method on ranked
+/// ballots. Each ballot counts toward its highest-ranked hopeful candidate.
+/// A candidate whose tally reaches the quota is elected, and the surplus above
+/// the quota is passed on: every ballot counting for that candidate continues to its
+/// next choice at a reduced weight (Gregory, or fractional, transfer). If nobody
+/// reaches the quota, the candidate with the lowest tally is excluded and their
+/// ballots move on at full weight. Once the hopeful candidates just fill the
+/// remaining seats, they are all elected.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct STV {
+    /// Defaults to Droop.
+    #[serde(default = "default_quota")]
+    pub quota: Quota,
+}
+
+/// The number of votes a candidate needs to be elected.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Quota {
+    /// floor(votes / (seats + 1)) + 1, the smallest quota that no more than
+    /// `seats` candidates can reach.
+    Droop,
+    /// votes / seats. Favors smaller factions a little more than Droop.
+    Hare,
+}
+
+fn default_quota() -> Quota {
+    Quota::Droop
+}
+
+impl Quota {
+    pub fn votes(&self, nvotes: f64, nseats: usize) -> f64 {
+        match self {
+            Quota::Droop => (nvotes / (nseats + 1) as f64).floor() + 1.0,
+            Quota::Hare => nvotes / nseats as f64,
+        }
+    }
+}
+
+pub struct STVSim {
+    p: STV,
+    weights: Vec<f64>,      // Current value of each ballot
+    counts_for: Vec<usize>, // The candidate each ballot counted for in the last tally
+    tallies: Vec<f64>,
+    hopeful: Vec<bool>,
+    winners: Vec<ElectResult>,
+}
+
+impl STV {
+    pub fn new_sim(&self, sim: &Sim) -> STVSim {
+        STVSim {
+            p: self.clone(),
+            weights: vec![1.0; sim.ncit],
+            counts_for: vec![0; sim.ncit],
+            tallies: vec![0.0; sim.ncand],
+            hopeful: vec![true; sim.ncand],
+            winners: Vec::with_capacity(sim.ncand),
+        }
+    }
+}
+
+impl MWMethodSim for STVSim {
+    fn multi_elect(
+        &mut self,
+        sim: &Sim,
+        _honest_rslt: Option<WinnerAndRunnerup>,
+        nwinners: usize,
+    ) -> &Vec<ElectResult> {
+        let quota = self.p.quota.votes(sim.ncit as f64, nwinners);
+        self.weights.fill(1.0);
+        self.hopeful.fill(true);
+        self.winners.clear();
+        let mut round = 0;
+        while self.winners.len() < nwinners {
+            round += 1;
+            self.tallies.fill(0.0);
+            for (icit, cand_fav_iter) in sim.ranks.lanes(Axis(1)).into_iter().enumerate() {
+                // Ballots rank every candidate, so there is always a hopeful one.
+                let icand = *cand_fav_iter.iter().find(|&&c| self.hopeful[c]).unwrap();
+                self.counts_for[icit] = icand;
+                self.tallies[icand] += self.weights[icit];
+            }
+            log::debug!(
+                "STV round {}: quota = {}, hopeful = {:?}, tallies = {:?}",
+                round,
+                quota,
+                self.hopeful,
+                self.tallies
+            );
+
+            let mut hopefuls: Vec<usize> = (0..sim.ncand).filter(|&c| self.hopeful[c]).collect();
+            // Sort from high to low tallies
+            hopefuls.sort_by(|&a, &b| self.tallies[b].partial_cmp(&self.tallies[a]).unwrap());
+            if self.winners.len() + hopefuls.len() <= nwinners {
+                log::debug!("  remaining hopefuls fill the seats: {:?}", hopefuls);
+                for &icand in hopefuls.iter() {
+                    self.hopeful[icand] = false;
+                    self.winners.push(ElectResult {
+                        cand: icand,
+                        score: self.tallies[icand],
+                    });
+                }
+                break;
+            }
+
+            let top_cand = hopefuls[0];
+            let top_votes = self.tallies[top_cand];
+            if top_votes >= quota {
+                let transfer = (top_votes - quota) / top_votes;
+                log::debug!(
+                    "  elected {} with {} votes, transferring surplus at {}",
+                    top_cand,
+                    top_votes,
+                    transfer
+                );
+                for (weight, &icand) in self.weights.iter_mut().zip(self.counts_for.iter()) {
+                    if icand == top_cand {
+                        *weight *= transfer;
+                    }
+                }
+                self.hopeful[top_cand] = false;
+                self.winners.push(ElectResult {
+                    cand: top_cand,
+                    score: top_votes,
+                });
+            } else {
+                let bot_cand = hopefuls[hopefuls.len() - 1];
+                log::debug!(
+                    "  excluded {} with {} votes",
+                    bot_cand,
+                    self.tallies[bot_cand]
+                );
+                self.hopeful[bot_cand] = false;
+            }
+        }
+        &self.winners
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::methods::test_utils::sim_from_scores;
+
+    #[test]
+    fn test_quota() {
+        assert_eq!(Quota::Droop.votes(20., 3), 6.);
+        assert_eq!(Quota::Droop.votes(100., 4), 21.);
+        assert_eq!(Quota::Hare.votes(100., 4), 25.);
+    }
+
+    #[test]
+    fn test_stv() {
+        // Example from https://en.wikipedia.org/wiki/Single_transferable_vote
+        // Oranges, Pears, Chocolate, Strawberries, Hamburgers.
+        // Preferences after those listed on the original ballots don't matter.
+        let mut sim = sim_from_scores(&[
+            (&[5., 4., 3., 2., 1.], 4), // O
+            (&[4., 5., 3., 2., 1.], 2), // P>O
+            (&[1., 2., 5., 4., 3.], 8), // C>S
+            (&[1., 2., 5., 3., 4.], 4), // C>H
+            (&[1., 2., 3., 5., 4.], 1), // S
+            (&[1., 2., 3., 4., 5.], 1), // H
+        ]);
+        sim.rank_candidates();
+        let mut stv = STV {
+            quota: Quota::Droop,
+        }
+        .new_sim(&sim);
+        let results = stv.multi_elect(&sim, None, 3);
+        // Round 1: Chocolate has 12, quota is 6, so half of each ballot transfers.
+        // Round 2: O 4, P 2, S 4+1, H 2+1 -- exclude Pears.
+        // Round 3: O 6 is elected with no surplus.
+        // Round 4: S 5, H 3 -- exclude Hamburgers, leaving Strawberries.
+        // Round 5: S 5 + 2 + 1
+        assert_eq!(
+            results,
+            &vec![
+                ElectResult {
+                    cand: 2,
+                    score: 12.
+                },
+                ElectResult { cand: 0, score: 6. },
+                ElectResult { cand: 3, score: 8. },
+            ]
+        );
+    }
+}
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::results::{ElectResult, WinnerAndRunnerup};
+use crate::sim::Sim;
 
 pub type Tallies = Vec<i32>;
 
-pub fn tally_votes(tallies: &Tallies) -> WinnerAndRunnerup {
+/// Finds the two candidates with the most votes. A tie for first is broken
+/// with a coin flip from the Sim.
+pub fn tally_votes(tallies: &Tallies, sim: &Sim) -> WinnerAndRunnerup {
     let ncand = tallies.len();
     let mut electee = 0usize;
     let mut most_votes = tallies[0];
@@ -29,7 +32,7 @@ pub fn tally_votes(tallies: &Tallies) -> WinnerAndRunnerup {
         }
     }
     if most_votes == runup_votes {
-        if rand::random() {
+        if sim.coin_flip() {
             // 50/50 chance
             (electee, runup) = (runup, electee);
             (most_votes, runup_votes) = (runup_votes, most_votes);
@@ -0,0 +1,122 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use ndarray::Array2;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+
+use crate::sim::SimRng;
+
+/// Voters' imperfect knowledge of the candidates. Voters cast their ballots on
+/// perceived utilities: their true utility for each candidate, plus a bias
+/// toward that candidate that every voter shares, plus noise of their own.
+/// Regrets are still measured with true utilities.
+///
+/// A voter's noise about a candidate has standard deviation
+/// noise * exp(voter_spread * z) / recognition, where z is a standard normal
+/// draw for each voter, and recognition is the candidate's.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Perception {
+    /// Standard deviation of a typical voter's noise about a well-known candidate
+    #[serde(default)]
+    pub noise: f64,
+    /// How much voters' noise levels vary, so that some voters are better
+    /// informed than others
+    #[serde(default)]
+    pub voter_spread: f64,
+    /// Each candidate's name recognition is uniform between min_recognition and 1.
+    /// Voters misjudge low-information candidates more.
+    #[serde(default = "default_recognition")]
+    pub min_recognition: f64,
+    /// Standard deviation of each candidate's systematic bias, a misperception
+    /// shared by all voters, as from slanted coverage
+    #[serde(default)]
+    pub bias: f64,
+}
+
+fn default_recognition() -> f64 {
+    1.0
+}
+
+impl Perception {
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("noise", self.noise),
+            ("voter_spread", self.voter_spread),
+            ("bias", self.bias),
+        ] {
+            if value.is_nan() || value < 0.0 {
+                return Err(format!(
+                    "Perception {} can't be negative, got {}",
+                    name, value
+                ));
+            }
+        }
+        if !(self.min_recognition > 0.0 && self.min_recognition <= 1.0) {
+            return Err(format!(
+                "Perception min_recognition must be above 0 and at most 1, got {}",
+                self.min_recognition
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fills scores with perceived utilities, given true utilities (cit, cand).
+    pub fn perceive(&self, utilities: &Array2<f64>, scores: &mut Array2<f64>, rng: &mut SimRng) {
+        let (ncit, ncand) = utilities.dim();
+        let mut bias = Vec::with_capacity(ncand);
+        let mut cand_noise = Vec::with_capacity(ncand);
+        for _ in 0..ncand {
+            let z: f64 = rng.sample(StandardNormal);
+            bias.push(z * self.bias);
+            let recognition = rng.random_range(self.min_recognition..=1.0);
+            cand_noise.push(self.noise / recognition);
+        }
+        for icit in 0..ncit {
+            let z: f64 = rng.sample(StandardNormal);
+            let voter_scale = (self.voter_spread * z).exp();
+            for icand in 0..ncand {
+                let z: f64 = rng.sample(StandardNormal);
+                scores[(icit, icand)] =
+                    utilities[(icit, icand)] + bias[icand] + z * voter_scale * cand_noise[icand];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::trial_rng;
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn test_perception() {
+        let perception: Perception = toml::from_str("bias = 1.0").unwrap();
+        assert!(perception.validate().is_ok());
+        let utilities = Array2::from_shape_fn((4, 3), |(icit, icand)| (icit * icand) as f64);
+        let mut scores = Array2::zeros((4, 3));
+        perception.perceive(&utilities, &mut scores, &mut trial_rng(6, 0));
+
+        // Bias alone shifts each candidate equally for every voter.
+        let shift = &scores - &utilities;
+        for icand in 0..3 {
+            for icit in 1..4 {
+                assert_float_eq!(shift[(icit, icand)], shift[(0, icand)], abs <= 1.0e-12);
+            }
+        }
+        assert_ne!(shift[(0, 0)], shift[(0, 1)]);
+
+        let perception: Perception = toml::from_str("noise = 1.0").unwrap();
+        perception.perceive(&utilities, &mut scores, &mut trial_rng(6, 0));
+        assert_ne!(
+            scores[(1, 1)] - utilities[(1, 1)],
+            scores[(2, 1)] - utilities[(2, 1)]
+        );
+
+        let perception: Perception = toml::from_str("min_recognition = 0.0").unwrap();
+        assert!(perception.validate().is_err());
+    }
+}
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use arrow_array::builder::{
-    BooleanBuilder, FixedSizeListBuilder, Float64Builder, Int32Builder, ListBuilder,
+    BooleanBuilder, FixedSizeListBuilder, Float64Builder, Int32Builder, ListBuilder, StringBuilder,
+    UInt64Builder,
 };
 use arrow_array::{RecordBatch, StructArray};
 use arrow_schema::{DataType, Field, SchemaBuilder};
+use meansd::MeanSD;
 use parquet::file::metadata::KeyValue;
 use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
-use core::task;
 use std::fs;
 use std::sync::mpsc;
 use std::{error::Error, sync::Arc};
@@ -17,53 +18,132 @@ use work_queue::Queue;
 use crate::config::Config;
 use crate::considerations::ConsiderationSim;
 use crate::cov_matrix::CovMatrix;
-use crate::method_tracker::{MethodTracker, SendableMethodReport};
-use crate::methods::Strategy;
-use crate::sim::Sim;
+use crate::method_tracker::{
+    new_committee_trackers, new_method_trackers, CommitteeTracker, MethodTracker,
+    SendableCommitteeReport, SendableMethodReport,
+};
+use crate::methods::{MWMethodSim, Strategy, WinnerAndRunnerup};
+use crate::sim::{trial_rng, Sim, SimRng};
+use crate::sweep::{describe, sweep_points, SweepPoint, SweepValue};
 
 static MAX_TRIALS_PER_JOB: usize = 10000;
 
 struct Task {
     config: Config,
+    point: usize,
+    sweep: Vec<(String, SweepValue)>,
+    width: usize,
+    first_trial: usize,
     trials: usize,
     result_chan: mpsc::Sender<TaskResult>,
 }
 
 struct TaskResult {
+    point: usize,
+    trial_summary: TrialSummary,
     method_stats: Vec<SendableMethodReport>,
+    committee_stats: Vec<SendableCommitteeReport>,
     batch: RecordBatch,
 }
 
+/// Statistics of the simulated elections themselves, apart from any method
+#[derive(Default)]
+struct TrialSummary {
+    ntrials: usize,
+    nno_winner: usize, // Trials without a Condorcet winner, from cycles or ties
+    smith_size: MeanSD,
+    salience_spread: Option<MeanSD>,
+}
+
+impl TrialSummary {
+    fn update(&mut self, sim: &Sim, salience_spread: Option<f64>) {
+        self.ntrials += 1;
+        let smith_size = sim.smith_set_size();
+        if smith_size > 1 {
+            self.nno_winner += 1;
+        }
+        self.smith_size.update(smith_size as f64);
+        if let Some(spread) = salience_spread {
+            self.salience_spread
+                .get_or_insert_with(MeanSD::default)
+                .update(spread);
+        }
+    }
+
+    fn combine(&mut self, other: &Self) {
+        self.ntrials += other.ntrials;
+        self.nno_winner += other.nno_winner;
+        self.smith_size += other.smith_size;
+        if let Some(other_spread) = other.salience_spread {
+            *self.salience_spread.get_or_insert_with(MeanSD::default) += other_spread;
+        }
+    }
+
+    fn report(&self) {
+        let salience = match &self.salience_spread {
+            Some(spread) => format!(", avg salience spread: {}", spread.mean()),
+            None => String::new(),
+        };
+        println!(
+            "Elections: {}, Frac no Condorcet winner: {}, avg Smith set size: {}{}",
+            self.ntrials,
+            self.nno_winner as f64 / self.ntrials as f64,
+            self.smith_size.mean(),
+            salience,
+        );
+    }
+}
+
 pub fn run(
     config: &Config,
     trials: usize,
     outfile: &Option<std::ffi::OsString>,
 ) -> Result<(), Box<dyn Error>> {
+    let mut config = config.clone();
+    if config.seed.is_none() {
+        // Keep seeds within TOML's integer range so they can be copied into a config file.
+        config.seed = Some(rand::random::<u64>() >> 1);
+    }
+    let config = &config;
+    let points = sweep_points(config)?;
+    check_sweep_columns(&points)?;
+    // Per-candidate columns are as wide as the largest candidate count in the sweep.
+    let width = points.iter().map(|p| p.config.candidates).max().unwrap();
+
     let num_workers = std::thread::available_parallelism().unwrap().get();
-    let min_chunks = num_workers.max((trials + MAX_TRIALS_PER_JOB - 1) / MAX_TRIALS_PER_JOB);
+    let total_trials = trials * points.len();
+    let min_chunks = num_workers.max((total_trials + MAX_TRIALS_PER_JOB - 1) / MAX_TRIALS_PER_JOB);
     let chunks_per_worker = (min_chunks + num_workers - 1) / num_workers;
     let chunks = chunks_per_worker * num_workers;
-    let trials_per_chunk = (trials + 1) / chunks;
+    let chunks_per_point = chunks.div_ceil(points.len()).min(trials.max(1));
+    let trials_per_chunk = (trials + 1) / chunks_per_point;
     log::info!(
-        "{} worker threads, {} batches of about {} events",
+        "{} worker threads, {} sweep points of {} batches of about {} events",
         num_workers,
-        chunks,
+        points.len(),
+        chunks_per_point,
         trials_per_chunk
     );
 
     let (task_result_tx, task_result_rx) = mpsc::channel();
 
     let queue: Queue<Task> = Queue::new(num_workers, 4);
-    let mut trials_left = trials;
-    for chunks_to_do in (1..chunks + 1).rev() {
-        let task_trials = (trials_left + chunks_to_do - 1) / chunks_to_do;
-        let task = Task {
-            config: config.clone(),
-            trials: task_trials,
-            result_chan: task_result_tx.clone(),
-        };
-        queue.push(task);
-        trials_left -= task_trials;
+    for (ipoint, point) in points.iter().enumerate() {
+        let mut trials_left = trials;
+        for chunks_to_do in (1..chunks_per_point + 1).rev() {
+            let task_trials = (trials_left + chunks_to_do - 1) / chunks_to_do;
+            let task = Task {
+                config: point.config.clone(),
+                point: ipoint,
+                sweep: point.values.clone(),
+                width,
+                first_trial: trials - trials_left,
+                trials: task_trials,
+                result_chan: task_result_tx.clone(),
+            };
+            queue.push(task);
+            trials_left -= task_trials;
+        }
     }
 
     let _handles: Vec<_> = queue
@@ -71,7 +151,16 @@ pub fn run(
         .map(|mut local_queue| {
             std::thread::spawn(move || {
                 while let Some(task) = local_queue.pop() {
-                    run_batch(&task.config, task.trials, &task.result_chan).unwrap();
+                    run_batch(
+                        &task.config,
+                        task.point,
+                        &task.sweep,
+                        task.width,
+                        task.first_trial,
+                        task.trials,
+                        &task.result_chan,
+                    )
+                    .unwrap();
                 }
             })
         })
@@ -81,7 +170,12 @@ pub fn run(
     // Loop over task_result_rx
 
     let mut writer = None;
-    let mut summaries: Option<Vec<SendableMethodReport>> = None;
+    let mut summaries: Vec<Option<Vec<SendableMethodReport>>> =
+        points.iter().map(|_| None).collect();
+    let mut committee_summaries: Vec<Option<Vec<SendableCommitteeReport>>> =
+        points.iter().map(|_| None).collect();
+    let mut trial_summaries: Vec<TrialSummary> =
+        points.iter().map(|_| TrialSummary::default()).collect();
     while let Ok(mut task_result) = task_result_rx.recv() {
         log::info!("Completed a batch of {} elections", task_result.method_stats[0].ntrials);
         if writer.is_none() {
@@ -92,14 +186,25 @@ pub fn run(
         if let Some(writer) = writer.as_mut() {
             writer.write(&task_result.batch)?;
         }
-        if let Some(summaries) = summaries.as_mut() {
+        trial_summaries[task_result.point].combine(&task_result.trial_summary);
+        if let Some(summaries) = summaries[task_result.point].as_mut() {
             for (whole_summary, task_summary) in
                 summaries.iter_mut().zip(task_result.method_stats.iter())
             {
                 whole_summary.combine(task_summary);
             }
         } else {
-            summaries = Some(std::mem::take(&mut task_result.method_stats));
+            summaries[task_result.point] = Some(std::mem::take(&mut task_result.method_stats));
+        }
+        if let Some(summaries) = committee_summaries[task_result.point].as_mut() {
+            for (whole_summary, task_summary) in
+                summaries.iter_mut().zip(task_result.committee_stats.iter())
+            {
+                whole_summary.combine(task_summary);
+            }
+        } else {
+            committee_summaries[task_result.point] =
+                Some(std::mem::take(&mut task_result.committee_stats));
         }
     }
 
@@ -116,111 +221,143 @@ pub fn run(
     //     handle.join().unwrap();
     // }
 
-    if let Some(summaries) = summaries {
-        for method_report in summaries {
-            method_report.report();
+    for (((point, summaries), committee_summaries), trial_summary) in points
+        .iter()
+        .zip(summaries)
+        .zip(committee_summaries)
+        .zip(trial_summaries)
+    {
+        if !point.values.is_empty() {
+            println!("Sweep point {}:", describe(&point.values));
+        }
+        if trial_summary.ntrials > 0 {
+            trial_summary.report();
+        }
+        if let Some(summaries) = summaries {
+            for method_report in summaries {
+                method_report.report();
+            }
+        }
+        for committee_report in committee_summaries.into_iter().flatten() {
+            committee_report.report();
         }
     }
+    println!("Seed: {}", config.seed.unwrap());
 
     Ok(())
 }
 
-fn run_batch(
-    config: &Config,
-    trials: usize,
-    task_result_tx: &mpsc::Sender<TaskResult>,
-) -> Result<(), Box<dyn Error>> {
-    let mut rng = rand::rng();
-
-    let ncand = config.candidates;
-    let ncit = config.voters;
-
-    let mut sim = Sim::new(ncand, ncit);
-
-    let mut sim_primary = if let Some(pcand) = config.primary_candidates {
-        Some(Sim::new(pcand, ncit))
-    } else {
-        None
-    };
-
-    let mut axes: Vec<Box<dyn ConsiderationSim>> = {
-        let max_sim = sim_primary.as_ref().unwrap_or(&sim);
-        config
-            .considerations
-            .iter()
-            .map(|c| c.new_sim(max_sim))
-            .collect()
-    };
-
-    let mut methods: Vec<MethodTracker> = config
-        .methods
-        .iter()
-        .map(|m| MethodTracker::new(m, &sim, trials))
-        .collect();
+/// Election holds the simulation state for one election at a time,
+/// and is reused from trial to trial.
+pub struct Election {
+    pub sim: Sim,
+    pub sim_primary: Option<Sim>,
+    pub axes: Vec<Box<dyn ConsiderationSim>>,
+    pub primary_method: Option<Box<dyn MWMethodSim>>,
+    pub methods: Vec<MethodTracker>,
+    pub results: Vec<Option<WinnerAndRunnerup>>, // Last result of each method
+    pub committees: Vec<CommitteeTracker>,
+    // ordered_final_cands is a list of candidates in order of increasing regret.
+    // With no primary, ordered_final_cands is identical to sim.cand_by_regret.
+    // With a primary, it's a list containing only winning primary candidates.
+    pub ordered_final_cands: Vec<usize>,
+    // The considerations' index of each candidate in the general election.
+    pub final_cands: Vec<usize>,
+}
 
-    // Create Arrow array builders:
-    let mut cov_bld = ListBuilder::new(ListBuilder::new(Float64Builder::new()));
-    let mut ideal_cnd_bld = Int32Builder::with_capacity(trials);
-    let mut cand_regret_bld = FixedSizeListBuilder::new(
-        Float64Builder::with_capacity(trials * sim.ncand),
-        sim.ncand as i32,
-    );
-    let mut cand_posn_blds = Vec::new();
-    for consid in axes.iter() {
-        cand_posn_blds.push(FixedSizeListBuilder::new(
-            FixedSizeListBuilder::new(
-                Float64Builder::with_capacity(trials * sim.ncand * consid.get_dim()),
-                consid.get_dim() as i32,
-            ),
-            sim.ncand as i32,
-        ));
-    }
-    let mut smith_candidates_bld = Int32Builder::with_capacity(trials);
-    let mut in_smith_set_bld = FixedSizeListBuilder::new(
-        BooleanBuilder::with_capacity(trials * sim.ncand),
-        sim.ncand as i32,
-    );
+impl Election {
+    pub fn new(config: &Config, max_trials: usize) -> Election {
+        let ncand = config.candidates;
+        let ncit = config.voters;
+
+        let mut sim = Sim::new(ncand, ncit);
+        sim.faction_strat_frac = config.faction_strat_frac.clone();
+        sim.combine = config.combine;
+        sim.perception = config.perception.clone();
+
+        let sim_primary = if let Some(pcand) = config.primary_candidates {
+            let mut sim_primary = Sim::new(pcand, ncit);
+            sim_primary.faction_strat_frac = config.faction_strat_frac.clone();
+            sim_primary.combine = config.combine;
+            sim_primary.perception = config.perception.clone();
+            Some(sim_primary)
+        } else {
+            None
+        };
 
-    let mut cov_matrix = CovMatrix::new(sim.ncand);
+        let axes: Vec<Box<dyn ConsiderationSim>> = {
+            let max_sim = sim_primary.as_ref().unwrap_or(&sim);
+            config
+                .considerations
+                .iter()
+                .map(|c| c.new_sim(max_sim))
+                .collect()
+        };
 
-    let mut mwms = if let Some(sim_primary) = &sim_primary {
-        Some(config.primary_method.new_sim(&sim_primary))
-    } else {
-        None
-    };
+        let primary_method = sim_primary
+            .as_ref()
+            .map(|sim_primary| config.primary_method.new_sim(sim_primary));
+        let methods = new_method_trackers(&config.methods, &sim, max_trials);
+        let results = vec![None; methods.len()];
+        let committees = new_committee_trackers(
+            &config.multi_winner_methods,
+            config.seats.unwrap_or(0),
+            &sim,
+            max_trials,
+        );
+
+        Election {
+            sim,
+            sim_primary,
+            axes,
+            primary_method,
+            methods,
+            results,
+            committees,
+            ordered_final_cands: vec![0; ncand],
+            final_cands: (0..ncand).collect(),
+        }
+    }
 
-    // ordered_final_cands is a list of candidates in order of increasing regret.
-    // With no primary, ordered_final_cands is identical to sim.cand_by_regret.
-    // With a primary, it's a list containing only winning primary candidates.
-    let mut ordered_final_cands = vec![0; sim.ncand];
+    /// Simulates trial itrial of the run with the given seed, including the
+    /// primary if there is one, and elects a winner with every method.
+    pub fn run_trial(&mut self, seed: u64, itrial: usize) {
+        self.run_election(&mut trial_rng(seed, itrial as u64));
+    }
 
-    for itrial in 0..trials {
-        log::debug!("Sim election {}", itrial + 1);
+    /// Simulates an election with random numbers from rng.
+    pub fn run_election(&mut self, rng: &mut SimRng) {
+        let sim = &mut self.sim;
 
-        if let Some(rrv) = &mut mwms {
-            let sim_primary: &mut Sim = sim_primary.as_mut().unwrap();
-            sim_primary.election(&mut axes, &mut rng);
-            let final_candidates = rrv.multi_elect(&sim_primary, None, sim.ncand);
+        if let Some(rrv) = &mut self.primary_method {
+            let sim_primary: &mut Sim = self.sim_primary.as_mut().unwrap();
+            sim_primary.election(&mut self.axes, rng);
+            let final_candidates = rrv.multi_elect(sim_primary, None, sim.ncand);
             log::debug!("primary election winners: {:?}", final_candidates);
-            sim.take_from_primary(sim_primary, &final_candidates);
+            sim.take_from_primary(sim_primary, final_candidates);
 
-            ordered_final_cands.clear();
+            self.final_cands.clear();
+            self.final_cands
+                .extend(final_candidates.iter().map(|c| c.cand));
+            self.ordered_final_cands.clear();
             for &fc in sim_primary.cand_by_regret.iter() {
                 if final_candidates.iter().any(|c| c.cand == fc) {
-                    ordered_final_cands.push(fc);
+                    self.ordered_final_cands.push(fc);
                 }
             }
         } else {
-            sim.election(&mut axes, &mut rng);
-            sim.cand_by_regret.clone_into(&mut ordered_final_cands);
+            sim.election(&mut self.axes, rng);
+            sim.cand_by_regret.clone_into(&mut self.ordered_final_cands);
         };
 
-        cov_matrix.compute(&sim.scores);
-        log::debug!("Cov matrix: {}", cov_matrix.elements);
-
         let mut prev_rslt = None;
-        for method in methods.iter_mut() {
-            let rslt = method.elect(&sim, prev_rslt);
+        for (imethod, method) in self.methods.iter_mut().enumerate() {
+            let honest_rslt = match method.prereq() {
+                Some(ipoll) => self.results[ipoll],
+                None => prev_rslt,
+            };
+            let rslt = method.elect(sim, honest_rslt);
+            self.results[imethod] = Some(rslt);
             let regret = sim.regrets[rslt.winner.cand];
             if let Strategy::Honest = method.method.strat() {
                 prev_rslt = Some(rslt);
@@ -232,12 +369,77 @@ fn run_batch(
                 regret
             );
         }
+        for committee in self.committees.iter_mut() {
+            committee.elect(sim);
+        }
+    }
+}
+
+/// Runs trials first_trial through first_trial + trials - 1 of the run, at one
+/// point of a sweep. Per-candidate columns are padded with nulls out to width.
+fn run_batch(
+    config: &Config,
+    point: usize,
+    sweep: &[(String, SweepValue)],
+    width: usize,
+    first_trial: usize,
+    trials: usize,
+    task_result_tx: &mpsc::Sender<TaskResult>,
+) -> Result<(), Box<dyn Error>> {
+    let seed = config.seed.unwrap();
+    let mut election = Election::new(config, trials);
+    let ncand = config.candidates;
+    // Constant for the config, but written on every row like the seed
+    let issues_eff_dim = config
+        .considerations
+        .iter()
+        .find_map(|c| c.issues_eff_dim());
+
+    // Create Arrow array builders:
+    let mut cov_bld = ListBuilder::new(ListBuilder::new(Float64Builder::new()));
+    let mut seed_bld = UInt64Builder::with_capacity(trials);
+    let mut trial_bld = UInt64Builder::with_capacity(trials);
+    let mut ideal_cnd_bld = Int32Builder::with_capacity(trials);
+    let mut eff_dim_bld = Float64Builder::with_capacity(trials);
+    let mut cand_regret_bld =
+        FixedSizeListBuilder::new(Float64Builder::with_capacity(trials * width), width as i32);
+    let mut cand_posn_blds = Vec::new();
+    for consid in election.axes.iter() {
+        cand_posn_blds.push(FixedSizeListBuilder::new(
+            FixedSizeListBuilder::new(
+                Float64Builder::with_capacity(trials * width * consid.get_dim()),
+                consid.get_dim() as i32,
+            ),
+            width as i32,
+        ));
+    }
+    let mut smith_candidates_bld = Int32Builder::with_capacity(trials);
+    let mut salience_bld = Float64Builder::with_capacity(trials);
+    let mut trial_summary = TrialSummary::default();
+    let mut in_smith_set_bld =
+        FixedSizeListBuilder::new(BooleanBuilder::with_capacity(trials * width), width as i32);
+
+    let mut cov_matrix = CovMatrix::new(ncand);
 
+    for itrial in first_trial..(first_trial + trials) {
+        log::debug!("Sim election {}", itrial + 1);
+        election.run_trial(seed, itrial);
+        let sim = &election.sim;
+
+        cov_matrix.compute(&sim.utilities);
+        log::debug!("Cov matrix: {}", cov_matrix.elements);
+
+        seed_bld.append_value(seed);
+        trial_bld.append_value(itrial as u64);
         ideal_cnd_bld.append_value(0);
+        eff_dim_bld.append_option(issues_eff_dim);
         let cbr = &sim.cand_by_regret;
         for &icand in cbr.iter() {
             cand_regret_bld.values().append_value(sim.regrets[icand]);
         }
+        for _ in ncand..width {
+            cand_regret_bld.values().append_null();
+        }
         cand_regret_bld.append(true);
         for ix in 0..sim.ncand {
             for iy in 0..(ix + 1) {
@@ -250,7 +452,7 @@ fn run_batch(
         }
         cov_bld.append(true); // End of matrix
 
-        for (consid, pos_bld) in axes.iter().zip(cand_posn_blds.iter_mut()) {
+        for (consid, pos_bld) in election.axes.iter().zip(cand_posn_blds.iter_mut()) {
             consid.push_posn_elements(
                 &mut |x, next_row| {
                     if x.is_nan() {
@@ -262,52 +464,107 @@ fn run_batch(
                         pos_bld.values().append(true);
                     }
                 },
-                &ordered_final_cands,
+                &election.ordered_final_cands,
             );
+            for _ in ncand..width {
+                for _ in 0..consid.get_dim() {
+                    pos_bld.values().values().append_null();
+                }
+                pos_bld.values().append(false);
+            }
             pos_bld.append(true);
         }
         smith_candidates_bld.append_value(sim.smith_set_size() as i32);
+        let salience_spread = election.axes.iter().find_map(|ax| ax.salience_spread());
+        salience_bld.append_option(salience_spread);
+        trial_summary.update(sim, salience_spread);
         for &icand in cbr.iter() {
             in_smith_set_bld
                 .values()
                 .append_value(sim.in_smith_set[icand]);
         }
+        for _ in ncand..width {
+            in_smith_set_bld.values().append_null();
+        }
         in_smith_set_bld.append(true);
     }
 
-    let mut columns: Vec<arrow_array::ArrayRef> = Vec::new();
-    columns.push(Arc::new(ideal_cnd_bld.finish()) as arrow_array::ArrayRef);
-    columns.push(Arc::new(cand_regret_bld.finish()) as arrow_array::ArrayRef);
+    let mut columns: Vec<arrow_array::ArrayRef> =
+        vec![Arc::new(seed_bld.finish()), Arc::new(trial_bld.finish())];
+    for (_, value) in sweep.iter() {
+        columns.push(match value {
+            SweepValue::Number(x) => {
+                let mut bld = Float64Builder::with_capacity(trials);
+                for _ in 0..trials {
+                    bld.append_value(*x);
+                }
+                Arc::new(bld.finish()) as arrow_array::ArrayRef
+            }
+            SweepValue::Text(s) => {
+                let mut bld = StringBuilder::new();
+                for _ in 0..trials {
+                    bld.append_value(s);
+                }
+                Arc::new(bld.finish()) as arrow_array::ArrayRef
+            }
+        });
+    }
+    columns.push(Arc::new(ideal_cnd_bld.finish()));
+    columns.push(Arc::new(cand_regret_bld.finish()));
     for cpb in cand_posn_blds.iter_mut() {
         columns.push(Arc::new(cpb.finish()) as arrow_array::ArrayRef);
     }
     columns.push(Arc::new(cov_bld.finish()) as arrow_array::ArrayRef);
     columns.push(Arc::new(smith_candidates_bld.finish()) as arrow_array::ArrayRef);
     columns.push(Arc::new(in_smith_set_bld.finish()) as arrow_array::ArrayRef);
+    columns.push(Arc::new(eff_dim_bld.finish()) as arrow_array::ArrayRef);
+    columns.push(Arc::new(salience_bld.finish()) as arrow_array::ArrayRef);
     let mut method_cols = Vec::new();
-    for method in methods.iter_mut() {
+    for method in election.methods.iter_mut().filter(|m| m.is_visible()) {
         method_cols.push((
-            Arc::new(Field::new(
-                method.colname(),
-                MethodTracker::data_type(),
-                false,
-            )),
+            Arc::new(Field::new(method.colname(), method.data_type(), false)),
             method.get_column(),
         ));
     }
     columns.push(Arc::new(StructArray::from(method_cols)));
+    if !election.committees.is_empty() {
+        let committee_cols: Vec<_> = election
+            .committees
+            .iter_mut()
+            .map(|committee| {
+                (
+                    Arc::new(Field::new(
+                        committee.colname(),
+                        committee.data_type(),
+                        false,
+                    )),
+                    committee.get_column(),
+                )
+            })
+            .collect();
+        columns.push(Arc::new(StructArray::from(committee_cols)));
+    }
 
     let mut schema = SchemaBuilder::new();
+    schema.push(Field::new("seed", DataType::UInt64, false));
+    schema.push(Field::new("trial", DataType::UInt64, false));
+    for (path, value) in sweep.iter() {
+        let data_type = match value {
+            SweepValue::Number(_) => DataType::Float64,
+            SweepValue::Text(_) => DataType::Utf8,
+        };
+        schema.push(Field::new(path, data_type, false));
+    }
     schema.push(Field::new("ideal_cand", DataType::Int32, true));
     schema.push(Field::new(
         "cand_regret",
         DataType::FixedSizeList(
             Arc::new(Field::new("item", DataType::Float64, true)),
-            sim.ncand as i32,
+            width as i32,
         ),
         true,
     ));
-    for consid in axes.iter() {
+    for consid in election.axes.iter() {
         schema.push(Field::new(
             consid.get_name(),
             DataType::FixedSizeList(
@@ -319,7 +576,7 @@ fn run_batch(
                     ),
                     true,
                 )),
-                sim.ncand as i32,
+                width as i32,
             ),
             true,
         ));
@@ -338,33 +595,58 @@ fn run_batch(
         "in_smith",
         DataType::FixedSizeList(
             Arc::new(Field::new("item", DataType::Boolean, true)),
-            sim.ncand as i32,
+            width as i32,
         ),
         true,
     ));
+    schema.push(Field::new("issues_eff_dim", DataType::Float64, true));
+    schema.push(Field::new("salience_spread", DataType::Float64, true));
 
-    //for method in methods.iter() {
+    //for method in election.methods.iter() {
     //    schema.push(method.get_field());
     //}
     let mut meth_schema_bld = SchemaBuilder::new();
-    for method in methods.iter() {
-        meth_schema_bld.push(Field::new(
-            method.colname(),
-            MethodTracker::data_type(),
-            false,
-        ));
+    for method in election.methods.iter().filter(|m| m.is_visible()) {
+        meth_schema_bld.push(Field::new(method.colname(), method.data_type(), false));
     }
     schema.push(Field::new(
         "methods",
         DataType::Struct(meth_schema_bld.finish().fields),
         false,
     ));
+    if !election.committees.is_empty() {
+        let mut committee_schema_bld = SchemaBuilder::new();
+        for committee in election.committees.iter() {
+            committee_schema_bld.push(Field::new(
+                committee.colname(),
+                committee.data_type(),
+                false,
+            ));
+        }
+        schema.push(Field::new(
+            "multi_winner_methods",
+            DataType::Struct(committee_schema_bld.finish().fields),
+            false,
+        ));
+    }
     let batch: RecordBatch = RecordBatch::try_new(Arc::new(schema.finish()), columns).unwrap();
-    let sendable_reports: Vec<SendableMethodReport> =
-        methods.iter().map(|m| m.sendable_report()).collect();
+    let sendable_reports: Vec<SendableMethodReport> = election
+        .methods
+        .iter()
+        .filter(|m| m.is_visible())
+        .map(|m| m.sendable_report())
+        .collect();
+    let committee_reports: Vec<SendableCommitteeReport> = election
+        .committees
+        .iter()
+        .map(|c| c.sendable_report())
+        .collect();
     task_result_tx
         .send(TaskResult {
+            point,
+            trial_summary,
             method_stats: sendable_reports,
+            committee_stats: committee_reports,
             batch,
         })
         .expect("Could not send batch summry");
@@ -372,6 +654,56 @@ fn run_batch(
     Ok(())
 }
 
+/// The names and types of the columns that come from the considerations,
+/// methods, and committees.
+fn config_columns(election: &Election) -> Vec<(String, DataType)> {
+    let mut columns: Vec<(String, DataType)> = election
+        .axes
+        .iter()
+        .map(|consid| {
+            let position = Field::new("item", DataType::Float64, true);
+            (
+                consid.get_name(),
+                DataType::FixedSizeList(Arc::new(position), consid.get_dim() as i32),
+            )
+        })
+        .collect();
+    for method in election.methods.iter().filter(|m| m.is_visible()) {
+        columns.push((method.colname(), method.data_type()));
+    }
+    for committee in election.committees.iter() {
+        columns.push((committee.colname(), committee.data_type()));
+    }
+    columns
+}
+
+/// Every sweep point is written under the schema of the first batch, so only
+/// per-candidate columns may vary, by padding. Sweeping a method setting that
+/// names its column, such as a strategy or nranks, or the dimensions of a
+/// consideration, would change the schema.
+fn check_sweep_columns(points: &[SweepPoint]) -> Result<(), Box<dyn Error>> {
+    let first = config_columns(&Election::new(&points[0].config, 1));
+    for point in points.iter().skip(1) {
+        let columns = config_columns(&Election::new(&point.config, 1));
+        if columns != first {
+            let names = |columns: &[(String, DataType)]| {
+                columns
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect::<Vec<_>>()
+            };
+            return Err(format!(
+                "Sweep point {} changes the output columns, from {:?} to {:?}",
+                describe(&point.values),
+                names(&first),
+                names(&columns)
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
 fn get_writer(
     config: &Config,
     filename: &std::ffi::OsStr,
@@ -389,3 +721,129 @@ fn get_writer(
     let writer = ArrowWriter::try_new(file, sample_batch.schema(), Some(props)).unwrap();
     writer
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn sweep_config(sweep: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+            voters = 11
+            candidates = 3
+            considerations = [{{ Issues = [{{ sigma = 1.0, halfcsep = 0.5 }}] }}]
+            methods = [{{ Range = {{ strat = "Honest", nranks = 3 }} }}]
+            {}
+            "#,
+            sweep
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sweep_output() {
+        let outfile =
+            std::env::temp_dir().join(format!("vote_sim_sweep_{}.parquet", std::process::id()));
+        let outfile = Some(outfile.into_os_string());
+
+        // Candidate counts only pad per-candidate columns, so both points share
+        // one schema.
+        let config = sweep_config(
+            r#"
+            [[sweep]]
+            path = "candidates"
+            values = [3, 5]
+            "#,
+        );
+        run(&config, 4, &outfile).unwrap();
+        std::fs::remove_file(outfile.as_ref().unwrap()).unwrap();
+
+        // Range names its column by its number of ranks.
+        let config = sweep_config(
+            r#"
+            [[sweep]]
+            path = "methods.0.Range.nranks"
+            values = [3, 5]
+            "#,
+        );
+        assert!(run(&config, 4, &outfile).is_err());
+
+        // More issues make a wider positions column.
+        let config = sweep_config(
+            r#"
+            [[sweep]]
+            path = "considerations.0.Issues"
+            values = [[{ sigma = 1.0, halfcsep = 0.5 }], [{ sigma = 1.0, halfcsep = 0.5 }, { sigma = 1.0, halfcsep = 0.5 }]]
+            "#,
+        );
+        assert!(run(&config, 4, &outfile).is_err());
+    }
+
+    #[test]
+    fn test_committee_output() {
+        let outfile = std::env::temp_dir().join(format!(
+            "vote_sim_committees_{}.parquet",
+            std::process::id()
+        ));
+        let outfile = Some(outfile.into_os_string());
+        let config: Config = toml::from_str(
+            r#"
+            voters = 11
+            candidates = 4
+            considerations = [{ Issues = [{ sigma = 1.0, halfcsep = 0.5 }] }]
+            methods = [{ Range = { strat = "Honest", nranks = 3 } }]
+            seats = 2
+            [[multi_winner_methods]]
+            IterativeRRV = { strat = "Honest", ranks = 3, k = 0.5 }
+            [[multi_winner_methods]]
+            PAV = {}
+            "#,
+        )
+        .unwrap();
+        let trials = 4;
+        run(&config, trials, &outfile).unwrap();
+
+        let file = fs::File::open(outfile.as_ref().unwrap()).unwrap();
+        let batches: Vec<RecordBatch> = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        fs::remove_file(outfile.as_ref().unwrap()).unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), trials);
+
+        for batch in batches.iter() {
+            let committees = batch
+                .column_by_name("multi_winner_methods")
+                .unwrap()
+                .as_struct();
+            // Only IterativeRRV reports iterations; PAV's are null.
+            for (name, iterates) in [("IterativeRRV", true), ("PAV", false)] {
+                let committee = committees.column_by_name(name).unwrap().as_struct();
+                let column = |field: &str| committee.column_by_name(field).unwrap();
+                let winners = column("winners").as_list::<i32>();
+                let prop_degree = column("prop_degree").as_list::<i32>();
+                let jr = column("jr").as_boolean();
+                let ejr = column("ejr").as_boolean();
+                let iterations = column("iterations").as_primitive::<Int32Type>();
+                let cycle_detected = column("cycle_detected").as_boolean();
+                for irow in 0..batch.num_rows() {
+                    let ranks = winners.value(irow);
+                    let ranks = ranks.as_primitive::<Int32Type>().values();
+                    assert_eq!(ranks.len(), 2);
+                    assert_ne!(ranks[0], ranks[1]);
+                    assert!(ranks.iter().all(|rank| (0..4).contains(rank)));
+                    assert_eq!(prop_degree.value(irow).len(), 2);
+                    assert!(jr.value(irow) || !ejr.value(irow), "EJR implies JR");
+                    assert_eq!(iterations.is_valid(irow), iterates);
+                    assert_eq!(cycle_detected.is_valid(irow), iterates);
+                }
+            }
+        }
+    }
+}
@@ -3,20 +3,57 @@
 
 use crate::considerations::*;
 use crate::methods::condorcet_util::mark_smith_candidates;
-use crate::methods::{ElectResult, WinnerAndRunnerup};
+use crate::methods::{ElectResult, Strategy, WinnerAndRunnerup};
+use crate::perception::Perception;
 use ndarray::{Array2, Axis};
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+/// The random number generator for everything in a simulated election.
+pub type SimRng = StdRng;
+
+/// Each trial gets its own generator, keyed by the run's seed and the trial index,
+/// so that a trial's election doesn't depend on how trials were split among threads.
+/// (Streams are only reproducible with the same version of rand.)
+pub fn trial_rng(seed: u64, trial: u64) -> SimRng {
+    let mut key = [0u8; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key[8..16].copy_from_slice(&trial.to_le_bytes());
+    SimRng::from_seed(key)
+}
 
 pub struct Sim {
     pub ncand: usize,
     pub ncit: usize,
+    /// Perceived utilities (cit, cand), which voters cast their ballots on
     pub scores: Array2<f64>,
+    /// True utilities, which regrets are measured by. Scores equal these
+    /// unless there's a perception model.
+    pub utilities: Array2<f64>,
     pub ranks: Array2<usize>,
     pub i_beats_j_by: Array2<i32>,
     pub regrets: Vec<f64>,
     pub cand_by_regret: Vec<usize>, // map from regret rank to icand
     pub regret_rank: Vec<usize>,    // map icand to regret-ranked pos'n
     pub in_smith_set: Vec<bool>,
+    /// A uniform draw in [0, 1) per voter. A voter is strategic under a method
+    /// when their draw is below the method's strategic fraction, so the same
+    /// voters turn strategic first in every method.
+    pub strat_draw: Vec<f64>,
+    pub nfactions: usize,
+    pub faction: Vec<usize>, // Faction of each voter, all 0 if no consideration has factions
+    /// Strategic fractions by faction. When set, these replace the fraction
+    /// of every strategic method.
+    pub faction_strat_frac: Option<Vec<f64>>,
+    /// How the considerations' utilities are combined into scores
+    pub combine: Combine,
+    combine_scratch: CombineScratch,
+    /// How voters misperceive their utilities
+    pub perception: Option<Perception>,
+    /// Breaks ties while counting votes. It's reseeded from the trial's generator
+    /// in every election, and is a RefCell because methods only borrow the Sim.
+    tie_rng: RefCell<SimRng>,
     scratch_ranks: Vec<usize>,
 }
 
@@ -26,18 +63,33 @@ impl Sim {
             ncand,
             ncit,
             scores: Array2::zeros((ncit, ncand)),
+            utilities: Array2::zeros((ncit, ncand)),
             i_beats_j_by: Array2::zeros((ncand, ncand)),
             ranks: Array2::zeros((ncit, ncand)),
             regrets: vec![0.0; ncand],
             cand_by_regret: (0..ncand).collect(),
             regret_rank: (0..ncand).collect(),
             in_smith_set: vec![false; ncand],
+            // Evenly spread until the first election, so that in tests the first
+            // voters are the strategic ones.
+            strat_draw: (0..ncit).map(|i| (i as f64 + 0.5) / ncit as f64).collect(),
+            nfactions: 1,
+            faction: vec![0; ncit],
+            faction_strat_frac: None,
+            combine: Combine::Add,
+            combine_scratch: CombineScratch::default(),
+            perception: None,
+            tie_rng: RefCell::new(SimRng::seed_from_u64(0)),
             scratch_ranks: (0..ncand).collect(),
         }
     }
 
-    pub fn election(&mut self, axes: &mut [Box<dyn ConsiderationSim>], rng: &mut ThreadRng) {
+    pub fn election(&mut self, axes: &mut [Box<dyn ConsiderationSim>], rng: &mut SimRng) {
         self.get_scores(axes, rng);
+        for draw in self.strat_draw.iter_mut() {
+            *draw = rng.random();
+        }
+        self.tie_rng = RefCell::new(SimRng::from_rng(rng));
         self.compute_regrets();
         self.rank_candidates();
         self.find_smith_set();
@@ -49,28 +101,53 @@ impl Sim {
         for (icand, winner) in winners.iter().enumerate() {
             for icit in 0..self.ncit {
                 self.scores[(icit, icand)] = primary.scores[(icit, winner.cand)];
+                self.utilities[(icit, icand)] = primary.utilities[(icit, winner.cand)];
             }
         }
+        self.strat_draw.clone_from(&primary.strat_draw);
+        self.tie_rng = RefCell::new(SimRng::from_rng(&mut *primary.tie_rng.borrow_mut()));
+        self.nfactions = primary.nfactions;
+        self.faction.clone_from(&primary.faction);
         self.compute_regrets();
         self.rank_candidates();
+        self.find_smith_set();
     }
 
-    fn get_scores(&mut self, axes: &mut [Box<dyn ConsiderationSim>], rng: &mut ThreadRng) {
-        self.scores.fill(0.0);
-        for ax in axes.iter_mut() {
-            ax.add_to_scores(&mut self.scores, rng);
+    fn get_scores(&mut self, axes: &mut [Box<dyn ConsiderationSim>], rng: &mut SimRng) {
+        self.utilities.fill(0.0);
+        self.combine
+            .add_to_scores(axes, &mut self.utilities, &mut self.combine_scratch, rng);
+        self.nfactions = 1;
+        self.faction.fill(0);
+        if let Some(ax) = axes.iter().find(|ax| ax.num_factions() > 1) {
+            self.nfactions = ax.num_factions();
+            for (icit, faction) in self.faction.iter_mut().enumerate() {
+                *faction = ax.voter_faction(icit);
+            }
         }
-        log::debug!("Voter utilities:\n{:?}", &mut self.scores);
+        if let Some(fractions) = &self.faction_strat_frac {
+            assert!(
+                fractions.len() == self.nfactions,
+                "faction_strat_frac has {} values, but voters form {} factions",
+                fractions.len(),
+                self.nfactions
+            );
+        }
+        match &self.perception {
+            Some(perception) => perception.perceive(&self.utilities, &mut self.scores, rng),
+            None => self.scores.assign(&self.utilities),
+        }
+        log::debug!("Voter utilities:\n{:?}", &mut self.utilities);
     }
 
-    // Side-effects: compute self.regrets and self.cand_by_regret
+    // Side-effects: compute self.regrets and self.cand_by_regret, from true utilities
     pub fn compute_regrets(&mut self) {
         let mut max_util = f64::MIN;
         let mut avg_util = 0.0;
         for j in 0..self.ncand {
             let mut ttl = 0.0;
             for i in 0..self.ncit {
-                ttl += self.scores[(i, j)];
+                ttl += self.utilities[(i, j)];
             }
             self.regrets[j] = ttl;
             if ttl > max_util {
@@ -123,6 +200,26 @@ impl Sim {
         }
     }
 
+    /// Whether voter icit casts a strategic ballot under strat. Per-faction
+    /// fractions take precedence over the method's fraction.
+    pub fn is_strategic(&self, icit: usize, strat: Strategy) -> bool {
+        match strat {
+            Strategy::Honest => false,
+            Strategy::Strategic { fraction } => {
+                let fraction = match &self.faction_strat_frac {
+                    Some(fractions) => fractions[self.faction[icit]],
+                    None => fraction,
+                };
+                self.strat_draw[icit] < fraction
+            }
+        }
+    }
+
+    /// A fair coin toss for breaking ties.
+    pub fn coin_flip(&self) -> bool {
+        self.tie_rng.borrow_mut().random()
+    }
+
     /// find_smith_set fills in in_smith_set array.
     /// Requires rank_candidates to have been called.
     pub fn find_smith_set(&mut self) {
@@ -166,6 +263,7 @@ impl Sim {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::considerations::issues::Issue;
     use ndarray::array;
 
     #[test]
@@ -203,4 +301,65 @@ mod tests {
             vec![true, true, true, false, false, false]
         );
     }
+
+    #[test]
+    fn test_faction_strategy() {
+        // With 20 candidates, both sides are practically certain to have some.
+        let mut sim = Sim::new(20, 8);
+        let issues = vec![
+            Issue {
+                sigma: 0.1,
+                halfcsep: 0.0,
+                halfvsep: Some(0.0),
+                uniform: false,
+                horizon: 1.0e100,
+            },
+            Issue {
+                sigma: 0.1,
+                halfcsep: 1.0,
+                halfvsep: Some(1.0),
+                uniform: false,
+                horizon: 1.0e100,
+            },
+        ];
+        let mut axes = vec![Consideration::Issues(Issues::List(issues)).new_sim(&sim)];
+        let mut rng = trial_rng(1, 0);
+        sim.election(&mut axes, &mut rng);
+        assert_eq!(sim.nfactions, 2);
+        // Voters are far from the other side's candidates, so each faction prefers its own.
+        for icit in 0..sim.ncit {
+            let fav_posn = sim.ranks[(icit, 0)];
+            let mut posns = Vec::new();
+            axes[0].push_posn_elements(&mut |x, _| posns.push(x), &vec![fav_posn]);
+            assert_eq!(posns[1] > 0.0, sim.faction[icit] == 1);
+        }
+
+        sim.faction = vec![0, 1, 0, 1, 0, 1, 0, 1];
+        sim.strat_draw = vec![0.5; 8];
+        let strat = Strategy::Strategic { fraction: 0.3 };
+        assert!(!sim.is_strategic(0, strat));
+        sim.faction_strat_frac = Some(vec![0.8, 0.0]);
+        assert!(sim.is_strategic(0, strat));
+        assert!(!sim.is_strategic(1, strat));
+        assert!(!sim.is_strategic(0, Strategy::Honest));
+    }
+
+    #[test]
+    fn test_trial_rng_replays() {
+        let config = [Consideration::Irrational(Irrational {
+            sigma: 1.0,
+            camps: 2,
+            individualism_deg: 30.0,
+        })];
+        let mut sims = Vec::new();
+        for trial in [3, 4, 3] {
+            let mut sim = Sim::new(4, 10);
+            let mut axes: Vec<_> = config.iter().map(|c| c.new_sim(&sim)).collect();
+            sim.election(&mut axes, &mut trial_rng(7, trial));
+            let coin_flips: Vec<bool> = (0..20).map(|_| sim.coin_flip()).collect();
+            sims.push((sim.scores, sim.strat_draw, coin_flips));
+        }
+        assert_eq!(sims[0], sims[2]);
+        assert_ne!(sims[0], sims[1]);
+    }
 }
@@ -0,0 +1,217 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::Config;
+
+/// One axis of a parameter sweep: a config key and the values it takes.
+///
+/// The path is a dotted list of keys into the config, with list elements
+/// selected by index, as in `voters`, `considerations.1.Issues.0.halfcsep`,
+/// or `considerations.0.Likability.mean`. Every point is written to one output
+/// file, so an axis can't change its columns: not a method's strategy or
+/// anything else in its column name, and not a consideration's dimensions.
+/// Rows are identified by the seed, the swept values, and the trial, which
+/// restarts from 0 at each point.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SweepAxis {
+    pub path: String,
+    #[serde(flatten)]
+    pub points: SweepPoints,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum SweepPoints {
+    /// Any values the key accepts, such as numbers or names
+    Values(Vec<Value>),
+    /// num evenly spaced numbers from start to stop, inclusive.
+    /// Whole numbers are written as integers, so counts can be swept too.
+    Linspace { start: f64, stop: f64, num: usize },
+}
+
+impl SweepAxis {
+    fn values(&self) -> Result<Vec<Value>, Box<dyn Error>> {
+        let values = match &self.points {
+            SweepPoints::Values(values) => values.clone(),
+            SweepPoints::Linspace { start, stop, num } => (0..*num)
+                .map(|i| {
+                    let x = if *num == 1 {
+                        *start
+                    } else {
+                        start + (stop - start) * i as f64 / (num - 1) as f64
+                    };
+                    json_number(x)
+                })
+                .collect(),
+        };
+        if values.is_empty() {
+            return Err(format!("sweep over {} has no values", self.path).into());
+        }
+        Ok(values)
+    }
+}
+
+fn json_number(x: f64) -> Value {
+    if x.fract() == 0.0 && x.abs() < 1.0e15 {
+        Value::from(x as i64)
+    } else {
+        Value::from(x)
+    }
+}
+
+/// The value of a swept key at one point, as written to its output column.
+/// An axis is numeric if all of its values are numbers.
+#[derive(Debug, Clone)]
+pub enum SweepValue {
+    Number(f64),
+    Text(String),
+}
+
+impl fmt::Display for SweepValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SweepValue::Number(x) => write!(f, "{}", x),
+            SweepValue::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// One combination of swept values, and the config it produces.
+#[derive(Debug, Clone)]
+pub struct SweepPoint {
+    pub config: Config,
+    /// The path and value of each sweep axis
+    pub values: Vec<(String, SweepValue)>,
+}
+
+/// Expands the config's sweep into the cartesian product of its axes, with the
+/// last axis varying fastest. Without a sweep there is a single point.
+pub fn sweep_points(config: &Config) -> Result<Vec<SweepPoint>, Box<dyn Error>> {
+    let mut base = config.clone();
+    let axes = base.sweep.take().unwrap_or_default();
+    let base_json = serde_json::to_value(&base)?;
+
+    let mut axis_values = Vec::new();
+    for axis in axes.iter() {
+        let values = axis.values()?;
+        let numeric = values.iter().all(|v| v.is_number());
+        let sweep_values: Vec<SweepValue> = values
+            .iter()
+            .map(|v| match v {
+                Value::Number(n) if numeric => SweepValue::Number(n.as_f64().unwrap()),
+                Value::String(s) => SweepValue::Text(s.clone()),
+                v => SweepValue::Text(v.to_string()),
+            })
+            .collect();
+        axis_values.push((values, sweep_values));
+    }
+
+    let mut points = Vec::new();
+    let mut index = vec![0; axes.len()];
+    loop {
+        let mut json = base_json.clone();
+        let mut values = Vec::new();
+        for (axis, ((json_values, sweep_values), &i)) in
+            axes.iter().zip(axis_values.iter().zip(index.iter()))
+        {
+            *lookup(&mut json, &axis.path)? = json_values[i].clone();
+            values.push((axis.path.clone(), sweep_values[i].clone()));
+        }
+        let config: Config = serde_json::from_value(json).map_err(|err| {
+            format!(
+                "Invalid config when sweeping {}: {}",
+                describe(&values),
+                err
+            )
+        })?;
+        config.validate()?;
+        points.push(SweepPoint { config, values });
+
+        // Advance like an odometer.
+        let mut iaxis = axes.len();
+        loop {
+            if iaxis == 0 {
+                return Ok(points);
+            }
+            iaxis -= 1;
+            index[iaxis] += 1;
+            if index[iaxis] < axis_values[iaxis].0.len() {
+                break;
+            }
+            index[iaxis] = 0;
+        }
+    }
+}
+
+/// Formats a point's swept values like "voters = 101, candidates = 5".
+pub fn describe(values: &[(String, SweepValue)]) -> String {
+    values
+        .iter()
+        .map(|(path, value)| format!("{} = {}", path, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn lookup<'a>(json: &'a mut Value, path: &str) -> Result<&'a mut Value, Box<dyn Error>> {
+    let mut node = json;
+    for key in path.split('.') {
+        node = match node {
+            Value::Object(map) => map.get_mut(key),
+            Value::Array(list) => key.parse::<usize>().ok().and_then(|i| list.get_mut(i)),
+            _ => None,
+        }
+        .ok_or_else(|| format!("Sweep path {} not found in the config (at {})", path, key))?;
+    }
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_points() {
+        let config: Config = toml::from_str(
+            r#"
+            voters = 11
+            candidates = 3
+            considerations = [{ Issues = [{ sigma = 1.0, halfcsep = 0.0 }] }]
+            methods = [{ Plurality = { strat = "Honest" } }]
+            [[sweep]]
+            path = "candidates"
+            values = [3, 5]
+            [[sweep]]
+            path = "considerations.0.Issues.0.halfcsep"
+            linspace = { start = 0.0, stop = 1.0, num = 3 }
+            [[sweep]]
+            path = "considerations.0.Issues.0.sigma"
+            values = [2.0]
+            "#,
+        )
+        .unwrap();
+        let points = sweep_points(&config).unwrap();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[4].config.candidates, 5);
+        assert!(points[4].config.sweep.is_none());
+        assert_eq!(
+            describe(&points[4].values),
+            "candidates = 5, considerations.0.Issues.0.halfcsep = 0.5, considerations.0.Issues.0.sigma = 2"
+        );
+        match &points[4].config.considerations[0] {
+            crate::considerations::Consideration::Issues(issues) => {
+                assert_eq!(issues.issues()[0].halfcsep, 0.5)
+            }
+            _ => panic!("Expected Issues"),
+        }
+
+        let mut bad = config.clone();
+        bad.sweep.as_mut().unwrap()[0].path = "candidate".to_string();
+        assert!(sweep_points(&bad).is_err());
+    }
+}
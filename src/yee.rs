@@ -0,0 +1,240 @@
+// © Copyright 2025 Topher Cawlfield
+// SPDX-License-Identifier: Apache-2.0
+
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::considerations::{Consideration, Issues};
+use crate::run::Election;
+use crate::sim::trial_rng;
+
+/// A Yee diagram holds candidates fixed in a two-issue space and sweeps the
+/// center of the voter population across a grid, recording who each method
+/// elects at every grid point.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Yee {
+    /// Candidate positions on the two issues
+    pub candidates: Vec<[f64; 2]>,
+    /// The grid spans -extent to +extent along both issues
+    #[serde(default = "default_extent")]
+    pub extent: f64,
+    /// Number of grid points along each issue
+    #[serde(default = "default_grid")]
+    pub grid: usize,
+}
+
+fn default_extent() -> f64 {
+    2.0
+}
+
+fn default_grid() -> usize {
+    41
+}
+
+impl Yee {
+    /// The voter offset along either issue at grid index i, at cell centers.
+    fn coord(&self, i: usize) -> f64 {
+        -self.extent + (i as f64 + 0.5) * 2.0 * self.extent / self.grid as f64
+    }
+
+    /// Maps an issue position to an SVG pixel, with the second issue increasing upward.
+    fn pixel(&self, x: f64, y: f64) -> (f64, f64) {
+        let scale = SVG_SIZE / (2.0 * self.extent);
+        ((x + self.extent) * scale, (self.extent - y) * scale)
+    }
+}
+
+const SVG_SIZE: f64 = 400.0;
+
+const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+
+/// Winners of each visible method, indexed [method][iy * grid + ix].
+struct YeeGrid {
+    colnames: Vec<String>,
+    winners: Vec<Vec<usize>>,
+}
+
+/// Runs a Yee diagram for the config's [yee] section, writing yee.csv and one
+/// yee_<method>.svg per method into outdir.
+pub fn yee(config: &Config, outdir: &Path) -> Result<(), Box<dyn Error>> {
+    let yee = config
+        .yee
+        .as_ref()
+        .ok_or("Yee diagrams need a [yee] section in the config")?;
+    let seed = config.seed.unwrap_or(0);
+    let grid = run_grid(config, yee, seed)?;
+
+    std::fs::create_dir_all(outdir)?;
+    let csv_path = outdir.join("yee.csv");
+    let mut writer = csv::Writer::from_path(&csv_path)?;
+    let mut header = vec!["x".to_string(), "y".to_string()];
+    header.extend(grid.colnames.iter().cloned());
+    writer.write_record(&header)?;
+    for iy in 0..yee.grid {
+        for ix in 0..yee.grid {
+            let mut record = vec![yee.coord(ix).to_string(), yee.coord(iy).to_string()];
+            record.extend(
+                grid.winners
+                    .iter()
+                    .map(|w| w[iy * yee.grid + ix].to_string()),
+            );
+            writer.write_record(&record)?;
+        }
+    }
+    writer.flush()?;
+    println!("Wrote {}", csv_path.display());
+
+    for (colname, winners) in grid.colnames.iter().zip(grid.winners.iter()) {
+        let svg_path = outdir.join(format!("yee_{}.svg", colname));
+        std::fs::write(&svg_path, render_svg(yee, winners))?;
+        println!("Wrote {}", svg_path.display());
+    }
+    println!("Seed: {}", seed);
+
+    Ok(())
+}
+
+/// Elects every method at every grid point. Each point reuses the same random
+/// numbers, so winners change only because the voters moved.
+fn run_grid(config: &Config, yee: &Yee, seed: u64) -> Result<YeeGrid, Box<dyn Error>> {
+    let issues: Issues = config
+        .considerations
+        .iter()
+        .find_map(|c| match c {
+            Consideration::Issues(issues) if issues.issues().len() == 2 => Some(issues.clone()),
+            _ => None,
+        })
+        .ok_or("Yee diagrams need an Issues consideration with exactly two issues")?;
+    let ncand = yee.candidates.len();
+    if ncand < 2 {
+        return Err("Yee diagrams need at least two candidates".into());
+    }
+    if ncand > PALETTE.len() {
+        return Err(format!("Yee diagrams support at most {} candidates", PALETTE.len()).into());
+    }
+    if yee.grid == 0 || yee.extent <= 0.0 {
+        return Err("Yee grid and extent must be positive".into());
+    }
+
+    // Only the spatial model matters here: other considerations and the primary are dropped.
+    let mut config = config.clone();
+    config.candidates = ncand;
+    config.primary_candidates = None;
+    config.considerations = vec![Consideration::Issues(issues.clone())];
+    let positions = Array2::from_shape_fn((ncand, 2), |(i, j)| yee.candidates[i][j]);
+
+    let mut election = Election::new(&config, 1);
+    let visible: Vec<usize> = (0..election.methods.len())
+        .filter(|&i| election.methods[i].is_visible())
+        .collect();
+    let mut winners = vec![Vec::with_capacity(yee.grid * yee.grid); visible.len()];
+    for iy in 0..yee.grid {
+        for ix in 0..yee.grid {
+            let mut axis = issues.new_sim(&election.sim);
+            axis.fix_candidates(&positions);
+            axis.set_voter_offset(&[yee.coord(ix), yee.coord(iy)]);
+            election.axes = vec![Box::new(axis)];
+            election.run_election(&mut trial_rng(seed, 0));
+            for (w, &imethod) in winners.iter_mut().zip(visible.iter()) {
+                w.push(election.results[imethod].unwrap().winner.cand);
+            }
+        }
+    }
+
+    Ok(YeeGrid {
+        colnames: visible
+            .iter()
+            .map(|&i| election.methods[i].colname())
+            .collect(),
+        winners,
+    })
+}
+
+fn render_svg(yee: &Yee, winners: &[usize]) -> String {
+    let cell = SVG_SIZE / yee.grid as f64;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+        SVG_SIZE
+    )
+    .unwrap();
+    for iy in 0..yee.grid {
+        for ix in 0..yee.grid {
+            // Overlap cells slightly so no seams show between them.
+            writeln!(
+                svg,
+                r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+                ix as f64 * cell,
+                (yee.grid - 1 - iy) as f64 * cell,
+                cell + 0.5,
+                cell + 0.5,
+                PALETTE[winners[iy * yee.grid + ix]]
+            )
+            .unwrap();
+        }
+    }
+    for (icand, posn) in yee.candidates.iter().enumerate() {
+        let (px, py) = yee.pixel(posn[0], posn[1]);
+        writeln!(
+            svg,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="8" fill="{}" stroke="black" stroke-width="2"/>"#,
+            px, py, PALETTE[icand]
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{:.2}" y="{:.2}" font-size="10" font-family="sans-serif" text-anchor="middle" dominant-baseline="central" fill="white">{}</text>"#,
+            px, py, icand
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yee_two_candidates() {
+        let config: Config = toml::from_str(
+            r#"
+            voters = 51
+            candidates = 3
+            considerations = [{ Issues = [
+                { sigma = 0.3, halfcsep = 0.0 },
+                { sigma = 0.3, halfcsep = 0.0 },
+            ] }]
+            methods = [{ Plurality = { strat = "Honest" } }]
+            [yee]
+            candidates = [[-1.0, 0.0], [1.0, 0.0]]
+            extent = 1.5
+            grid = 3
+            "#,
+        )
+        .unwrap();
+        let yee = config.yee.as_ref().unwrap();
+        assert_eq!(yee.coord(0), -1.0);
+        assert_eq!(yee.coord(1), 0.0);
+        assert_eq!(yee.pixel(-1.5, 1.5), (0.0, 0.0));
+
+        let grid = run_grid(&config, yee, 1).unwrap();
+        assert_eq!(grid.colnames.len(), 1);
+        let winners = &grid.winners[0];
+        for iy in 0..3 {
+            assert_eq!(winners[iy * 3], 0);
+            assert_eq!(winners[iy * 3 + 2], 1);
+        }
+    }
+}